@@ -14,6 +14,13 @@ use crate::core::Dependency;
 use crate::core::Source;
 use crate::git;
 
+/// Shorthand prefixes for git repositories on common hosts (see [`Uri::parse`]).
+const URI_SHORTHAND_HOSTS: &[(&str, &str)] = &[
+    ("cb:", "codeberg.org"),
+    ("gh:", "github.com"),
+    ("gl:", "gitlab.com"),
+];
+
 /* -------------------------------------------------------------------------- */
 /*                                Struct: Args                                */
 /* -------------------------------------------------------------------------- */
@@ -62,6 +69,16 @@ pub struct SourceArgs {
     #[arg(short, long, value_name = "NAME")]
     pub name: Option<String>,
 
+    /// Enable the addon's `FEATURES` (can be specified more than once and
+    /// accepts multiple values delimited by `,`).
+    #[arg(short = 'F', long, value_name = "FEATURES", value_delimiter = ',', num_args = 1..)]
+    pub features: Vec<String>,
+
+    /// Don't initialize the git submodules of the addon's repository (only
+    /// used with a git repository `URI`)
+    #[arg(long)]
+    pub no_submodules: bool,
+
     #[clap(flatten)]
     pub rev: GitRevArgs,
 
@@ -69,31 +86,51 @@ pub struct SourceArgs {
     pub release: ReleaseArgs,
 }
 
-impl From<SourceArgs> for Dependency {
-    fn from(value: SourceArgs) -> Self {
+impl TryFrom<SourceArgs> for Dependency {
+    type Error = anyhow::Error;
+
+    fn try_from(value: SourceArgs) -> Result<Self, Self::Error> {
         let source = match value.uri {
             Uri::Path(path) => path.into(),
-            Uri::Url(repo) => match (value.release.release, value.release.asset) {
-                (Some(tag), Some(asset)) => Source::Release(
+            Uri::Url(repo, rev) => match (value.release.release, value.release.asset) {
+                (Some(_), _) if rev.is_some() || value.rev.is_some() => {
+                    return Err(anyhow!("conflicting revisions specified for: {}", repo))
+                }
+                (Some(tag), asset) => Source::Release(
                     git::GitHubRelease::builder()
                         .repo(repo.into())
                         .tag(tag)
                         .asset(asset)
                         .build(),
                 ),
-                _ => Source::Git(
-                    git::Source::builder()
-                        .repo(repo.into())
-                        .reference(<Option<git::Reference>>::from(value.rev))
-                        .build(),
-                ),
+                _ => {
+                    let repo = git::Remote::from(repo);
+
+                    let reference = match (<Option<git::Reference>>::from(value.rev), rev) {
+                        (Some(_), Some(_)) => {
+                            return Err(anyhow!("conflicting revisions specified for: {}", repo))
+                        }
+                        (Some(r), None) => Some(r),
+                        (None, Some(r)) => Some(git::Reference::resolve(&repo, &r)?),
+                        (None, None) => None,
+                    };
+
+                    Source::Git(
+                        git::Source::builder()
+                            .repo(repo)
+                            .reference(reference)
+                            .submodules(value.no_submodules.then_some(false))
+                            .build(),
+                    )
+                }
             },
         };
 
-        Dependency::builder()
+        Ok(Dependency::builder()
             .addon(value.name.or(source.name()))
+            .features(value.features)
             .source(source)
-            .build()
+            .build())
     }
 }
 
@@ -102,12 +139,17 @@ impl From<SourceArgs> for Dependency {
 #[derive(clap::Args, Debug)]
 #[group(required = false, multiple = true)]
 pub struct ReleaseArgs {
-    /// Use a git `RELEASE` version (only used with a git repository `URI`)
-    #[arg(long = "release", value_name = "RELEASE", requires = "asset")]
+    /// Use a git `RELEASE` version, which can be an exact tag, `latest`, or a
+    /// semantic version requirement like `^1.2`; the `sha256` checksum of an
+    /// exact tag's asset is recorded in the manifest (only used with a git
+    /// repository `URI`)
+    #[arg(long = "release", value_name = "RELEASE")]
     pub release: Option<String>,
 
-    /// A specific `ASSET` from a git `RELEASE` version (only used with a git
-    /// repository `URI` and `RELEASE`)
+    /// A specific `ASSET` from a git `RELEASE` version, which can be a glob
+    /// pattern or `source` for the release's source archive; if omitted, the
+    /// release's only `.zip` asset is used (only used with a git repository
+    /// `URI` and `RELEASE`)
     #[arg(long, value_name = "ASSET", requires = "release")]
     pub asset: Option<String>,
 }
@@ -131,6 +173,15 @@ pub struct GitRevArgs {
     pub tag: Option<String>,
 }
 
+/* ---------------------------- Impl: GitRevArgs ---------------------------- */
+
+impl GitRevArgs {
+    /// `is_some` returns whether any git revision was specified.
+    pub fn is_some(&self) -> bool {
+        self.branch.is_some() || self.rev.is_some() || self.tag.is_some()
+    }
+}
+
 impl From<GitRevArgs> for Option<git::Reference> {
     fn from(value: GitRevArgs) -> Self {
         match value {
@@ -160,9 +211,15 @@ pub fn handle(project: Option<impl AsRef<Path>>, mut args: Args) -> anyhow::Resu
         .relative_to(&path_manifest)
         .map_err(|e| anyhow!(e))?;
 
-    let mut dep = Dependency::from(args.source).rooted_at(&path_project);
+    let mut dep = Dependency::try_from(args.source)?.rooted_at(&path_project);
     dep.is_direct = true;
 
+    // Pin release assets to the checksum of their first download so that
+    // later installs detect a modified asset.
+    if let Source::Release(release) = &mut dep.source {
+        release.pin()?;
+    }
+
     // Determine whether an installation is required by default. This is the
     // case when there is no "addons" directory or the [`Addon`] isn't found.
     let path_addons = path_project.as_path().join("addons");
@@ -256,10 +313,12 @@ pub fn handle(project: Option<impl AsRef<Path>>, mut args: Args) -> anyhow::Resu
 /*                                  Enum: Uri                                 */
 /* -------------------------------------------------------------------------- */
 
-/// Uri contains a specification of where the addon source code is located.
+/// Uri contains a specification of where the addon source code is located. A
+/// [`Uri::Url`] may include a git revision parsed from a shorthand source (see
+/// [`Uri::parse`]).
 #[derive(Clone, Debug, PartialEq)]
 pub enum Uri {
-    Url(Url),
+    Url(Url, Option<String>),
     Path(PathBuf),
 }
 
@@ -268,26 +327,60 @@ pub enum Uri {
 impl Uri {
     /* --------------------------- Methods: Public -------------------------- */
 
-    /// Parse either a [`Url`] or a [`PathBuf`] from the provided [`str`].
+    /// Parse either a [`Url`] or a [`PathBuf`] from the provided [`str`]. Note
+    /// that SCP-style git remotes (e.g. `git@github.com:owner/repo.git`) are
+    /// parsed into the equivalent `ssh://` [`Url`] and that paths to local bare
+    /// git repositories are parsed into `file://` [`Url`]s.
+    ///
+    /// The following shorthands for `https` git repositories are supported,
+    /// each of which accepts an optional `@REF` suffix specifying a tag,
+    /// branch, or commit:
+    ///     - `gh:owner/repo` for GitHub
+    ///     - `gl:owner/repo` for GitLab
+    ///     - `cb:owner/repo` for Codeberg
+    ///     - `owner/repo` for the configured default host, but only if no such
+    ///       directory exists
+    ///
+    /// Note that the prefixed shorthands also accept nested namespaces (e.g.
+    /// `gl:group/subgroup/repo`).
     pub fn parse(s: &str) -> Result<Uri, UriError> {
+        for (prefix, host) in URI_SHORTHAND_HOSTS {
+            if let Some(path) = s.strip_prefix(prefix) {
+                return Uri::parse_shorthand(host, path, true)
+                    .ok_or(UriError::Invalid(s.to_owned()));
+            }
+        }
+
         // NOTE: Parse a `Url` first as it's more specific than a `PathBuf`.
-        if let Ok(u) = Url::parse(s) {
-            return Ok(Uri::Url(u));
+        if let Ok(r) = s.parse::<git::Remote>() {
+            return Ok(Uri::Url(r.url().clone(), None));
         }
 
         // TODO: Properly identify the plugin path according to documentation.
-        if let Ok(p) = PathBuf::from_str(s) {
-            if !p.exists() {
-                return Err(UriError::NotFound(s.to_owned()));
-            }
+        let p = PathBuf::from(s);
 
-            if !p.is_dir() {
-                return Err(UriError::NotADir(s.to_owned()));
-            }
+        if !p.exists() {
+            let host = git::get_settings()
+                .map(|s| s.default_host().to_owned())
+                .map_err(|e| UriError::Invalid(e.to_string()))?;
+
+            return Uri::parse_shorthand(&host, s, false).ok_or(UriError::NotFound(s.to_owned()));
+        }
+
+        if !p.is_dir() {
+            return Err(UriError::NotADir(s.to_owned()));
+        }
 
-            if let Ok(p) = p.canonicalize() {
-                return Ok(Uri::Path(p));
+        if let Ok(p) = p.canonicalize() {
+            // A bare git repository has no working tree to install from, so
+            // treat it as a local git remote instead.
+            if git2::Repository::open_bare(&p).is_ok() {
+                return Url::from_file_path(&p)
+                    .map(|u| Uri::Url(u, None))
+                    .map_err(|_| UriError::Invalid(s.to_owned()));
             }
+
+            return Ok(Uri::Path(p));
         }
 
         Err(UriError::Invalid(s.to_owned()))
@@ -298,7 +391,7 @@ impl Uri {
     pub fn relative_to(&mut self, base: impl AsRef<Path>) -> std::io::Result<()> {
         let path = match self {
             Uri::Path(p) => p.canonicalize()?,
-            Uri::Url(_) => {
+            Uri::Url(..) => {
                 return Ok(());
             }
         };
@@ -333,6 +426,37 @@ impl Uri {
             };
         }
     }
+
+    /* -------------------------- Methods: Private -------------------------- */
+
+    /// Parses a `namespace/repo[@REF]` shorthand for a repository on `host`.
+    /// If `nested` isn't set, then the namespace must be a single segment.
+    fn parse_shorthand(host: &str, s: &str, nested: bool) -> Option<Uri> {
+        let (path, rev) = match s.split_once('@') {
+            None => (s, None),
+            Some((path, rev)) => (path, Some(rev.to_owned())),
+        };
+
+        let is_valid_segment = |s: &str| {
+            !s.is_empty()
+                && !s.starts_with('.')
+                && s.chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        };
+
+        let segments = path.split('/').collect::<Vec<_>>();
+        if segments.len() < 2
+            || (!nested && segments.len() > 2)
+            || !segments.iter().all(|s| is_valid_segment(s))
+            || rev.as_ref().is_some_and(|r| r.is_empty())
+        {
+            return None;
+        }
+
+        Url::parse(&format!("https://{}/{}", host, path))
+            .ok()
+            .map(|u| Uri::Url(u, rev))
+    }
 }
 
 /* ----------------------------- Enum: UriError ----------------------------- */
@@ -361,6 +485,12 @@ mod tests {
     use std::io::Write;
     use std::path::PathBuf;
 
+    use url::Url;
+
+    use super::Dependency;
+    use super::GitRevArgs;
+    use super::ReleaseArgs;
+    use super::SourceArgs;
     use super::Uri;
 
     macro_rules! write_file {
@@ -374,6 +504,132 @@ mod tests {
         };
     }
 
+    /* -------------------------- Test: Uri::parse -------------------------- */
+
+    #[rstest]
+    #[case("gh:owner/repo", "https://github.com/owner/repo", None)]
+    #[case("gl:owner/repo@main", "https://gitlab.com/owner/repo", Some("main"))]
+    #[case("gl:studio/tools/ui", "https://gitlab.com/studio/tools/ui", None)]
+    #[case(
+        "cb:owner/repo.gd@v1.2.3",
+        "https://codeberg.org/owner/repo.gd",
+        Some("v1.2.3")
+    )]
+    #[case(
+        "git@github.com:owner/repo.git",
+        "ssh://git@github.com/owner/repo.git",
+        None
+    )]
+    fn test_uri_parse_shorthand(#[case] input: &str, #[case] url: &str, #[case] rev: Option<&str>) {
+        // Given: A git source specified with a shorthand.
+        // When: The source is parsed.
+        let got = Uri::parse(input).unwrap();
+
+        // Then: The canonical repository URL and revision are returned.
+        assert_eq!(
+            got,
+            Uri::Url(Url::parse(url).unwrap(), rev.map(str::to_owned))
+        );
+    }
+
+    #[rstest]
+    #[case("gh:owner")]
+    #[case("gh:owner/repo@")]
+    #[case("gh:owner//repo")]
+    #[case("owner/repo/extra")]
+    #[case("../missing/path")]
+    fn test_uri_parse_shorthand_rejects_invalid(#[case] input: &str) {
+        // Given: An invalid shorthand or a missing path.
+        // When: The source is parsed.
+        let got = Uri::parse(input);
+
+        // Then: The source is rejected.
+        assert!(got.is_err());
+    }
+
+    #[test]
+    fn test_uri_parse_local_bare_repository() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A local bare git repository.
+        let path = tmp.path().join("addon.git");
+        git2::Repository::init_bare(&path).unwrap();
+
+        // When: The repository's path is parsed.
+        let got = Uri::parse(path.to_str().unwrap()).unwrap();
+
+        // Then: The repository is treated as a 'file://' remote.
+        assert_eq!(
+            got,
+            Uri::Url(
+                Url::from_file_path(path.canonicalize().unwrap()).unwrap(),
+                None
+            )
+        );
+    }
+
+    /* --------------- Test: Dependency::try_from(SourceArgs) --------------- */
+
+    #[rstest]
+    #[case(Some("v1.0.0"), None)]
+    #[case(None, Some("v1.0.0"))]
+    fn test_dependency_from_release_with_revision_fails(
+        #[case] shorthand: Option<&str>,
+        #[case] tag: Option<&str>,
+    ) {
+        // Given: A release source which also specifies a git revision, either
+        // with a shorthand or a flag.
+        let args = source_args(
+            Uri::Url(
+                Url::parse("https://github.com/owner/repo").unwrap(),
+                shorthand.map(str::to_owned),
+            ),
+            tag,
+            Some("v1.0.0"),
+        );
+
+        // When: The dependency is created.
+        let got = Dependency::try_from(args);
+
+        // Then: The conflicting revisions are rejected.
+        assert!(got.is_err_and(|e| e.to_string().contains("conflicting revisions")));
+    }
+
+    #[test]
+    fn test_dependency_from_shorthand_revision_propagates_errors() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A shorthand revision of a repository which can't be reached.
+        let url = Url::from_file_path(tmp.path().join("missing.git")).unwrap();
+        let args = source_args(Uri::Url(url, Some("v1.0.0".to_owned())), None, None);
+
+        // When: The dependency is created.
+        let got = Dependency::try_from(args);
+
+        // Then: The error resolving the revision is returned.
+        assert!(got.is_err());
+    }
+
+    /* ------------------------ Function: source_args ----------------------- */
+
+    fn source_args(uri: Uri, tag: Option<&str>, release: Option<&str>) -> SourceArgs {
+        SourceArgs {
+            uri,
+            name: None,
+            features: vec![],
+            no_submodules: false,
+            rev: GitRevArgs {
+                branch: None,
+                rev: None,
+                tag: tag.map(str::to_owned),
+            },
+            release: ReleaseArgs {
+                release: release.map(str::to_owned),
+                asset: None,
+            },
+        }
+    }
+
     /* ----------------------- Test: Uri::relative_to ----------------------- */
 
     #[rstest]
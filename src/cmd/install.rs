@@ -5,6 +5,7 @@ use typed_builder::TypedBuilder;
 use crate::config::manifest::Manifest;
 use crate::config::Configuration;
 use crate::config::Parsable;
+use crate::core::Resolution;
 
 /* -------------------------------------------------------------------------- */
 /*                                Struct: Args                                */
@@ -12,11 +13,28 @@ use crate::config::Parsable;
 
 #[derive(clap::Args, Debug, TypedBuilder)]
 pub struct Args {
+    /// Warn instead of failing when an addon's peer addons are not installed.
+    #[arg(long)]
+    #[builder(default)]
+    pub allow_missing_peers: bool,
+
+    /// Enable the project's `FEATURES` (can be specified more than once and
+    /// accepts multiple values delimited by `,`).
+    #[arg(short = 'F', long, value_name = "FEATURES", value_delimiter = ',', num_args = 1..)]
+    #[builder(default)]
+    pub features: Vec<String>,
+
     /// Add a development-only dependency (will not be propagated to dependents'
     /// installs).
     #[arg(long, alias = "prod")]
     pub production: bool,
 
+    /// Select the `STRATEGY` used to choose between compatible versions of an
+    /// addon required by multiple dependencies.
+    #[arg(long, value_enum, value_name = "STRATEGY", default_value_t)]
+    #[builder(default)]
+    pub resolve: Resolution,
+
     /// Add the dependency only for `TARGET` (can be specified more than once
     /// and accepts multiple values delimited by `,`).
     #[arg(short, long, value_name = "TARGET", value_delimiter = ',', num_args = 1..)]
@@ -43,8 +61,11 @@ pub fn handle(project: Option<impl AsRef<Path>>, args: Args) -> anyhow::Result<(
     };
 
     let install = crate::core::Install::builder()
+        .allow_missing_peers(args.allow_missing_peers)
         .dev(!args.production)
+        .features(args.features.iter().map(String::as_str).collect())
         .manifest(&m)
+        .resolution(args.resolve)
         .targets(targets)
         .build();
 
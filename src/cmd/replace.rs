@@ -8,6 +8,7 @@ use crate::config::Parsable;
 use crate::config::Persistable;
 use crate::core::Addon;
 use crate::core::Dependency;
+use crate::core::Source;
 
 use super::add::SourceArgs;
 
@@ -54,7 +55,7 @@ pub fn handle(project: Option<impl AsRef<Path>>, mut args: Args) -> anyhow::Resu
         .relative_to(&path_manifest)
         .map_err(|e| anyhow!(e))?;
 
-    let mut dep = Dependency::from(args.source).rooted_at(&path_project);
+    let mut dep = Dependency::try_from(args.source)?.rooted_at(&path_project);
     dep.is_direct = true;
     dep.replace = Some(args.addon.clone());
 
@@ -62,6 +63,12 @@ pub fn handle(project: Option<impl AsRef<Path>>, mut args: Args) -> anyhow::Resu
         let _ = dep.replace.take();
     }
 
+    // Pin release assets to the checksum of their first download so that
+    // later installs detect a modified asset.
+    if let Source::Release(release) = &mut dep.source {
+        release.pin()?;
+    }
+
     // Determine whether an installation is required by default. This is the
     // case when there is no "addons" directory or the [`Addon`] isn't found.
     let path_addons = path_project.as_path().join("addons");
@@ -1,17 +1,21 @@
 use anyhow::anyhow;
 use ini::Ini;
+use semver::Version;
+use std::path::Component;
+use std::path::PathBuf;
 
 use super::Configuration;
 use super::Parsable;
 use super::ParsableError;
 
 const GDEXTENSION_FILE_EXTENSION: &str = "gdextension";
+const GDEXTENSION_SECTIONS_REFERENCES: &[&str] = &["libraries", "dependencies"];
+const GODOT_RESOURCE_PREFIX: &str = "res://";
 
 /* -------------------------------------------------------------------------- */
 /*                              Struct: Extension                             */
 /* -------------------------------------------------------------------------- */
 
-#[allow(dead_code)]
 #[derive(Clone, Debug, Default)]
 pub struct Extension(Ini);
 
@@ -21,6 +25,30 @@ impl Extension {
     pub fn extension() -> &'static str {
         GDEXTENSION_FILE_EXTENSION
     }
+
+    /// `compatibility_minimum` returns the minimum _Godot_ version which is
+    /// able to load the extension, if specified.
+    pub fn compatibility_minimum(&self) -> Option<Version> {
+        self.0
+            .section(Some("configuration"))
+            .and_then(|s| s.get("compatibility_minimum"))
+            .and_then(super::godot::parse_version)
+    }
+
+    /// `references` returns the paths, relative to the _Godot_ project root, of
+    /// the files referenced by the extension's libraries and dependencies (i.e.
+    /// its `res://` paths). Paths which would escape the project are skipped.
+    pub fn references(&self) -> Vec<PathBuf> {
+        GDEXTENSION_SECTIONS_REFERENCES
+            .iter()
+            .filter_map(|s| self.0.section(Some(*s)))
+            .flat_map(|s| s.iter().map(|(_, v)| v))
+            .flat_map(|v| v.split('"'))
+            .filter_map(|v| v.trim().strip_prefix(GODOT_RESOURCE_PREFIX))
+            .map(PathBuf::from)
+            .filter(|p| p.components().all(|c| matches!(c, Component::Normal(_))))
+            .collect()
+    }
 }
 
 /* --------------------------- Impl: Configuration -------------------------- */
@@ -42,3 +70,48 @@ impl Parsable for Extension {
             .map_err(|e| ParsableError::Parse(anyhow!(e)))
     }
 }
+
+/* -------------------------------------------------------------------------- */
+/*                                 Mod: Tests                                 */
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::Extension;
+    use crate::config::Parsable;
+
+    /* --------------------- Test: Extension::references -------------------- */
+
+    #[test]
+    fn test_extension_references_returns_resource_paths() {
+        // Given: An extension with libraries and dependencies.
+        let extension = Extension::parse(
+            r#"
+[configuration]
+entry_symbol = "init"
+
+[libraries]
+linux.x86_64 = "res://bin/libaddon.so"
+windows.x86_64 = "res://../escape.dll"
+
+[dependencies]
+linux.x86_64 = { "res://bin/libdep.so" : "" }
+"#,
+        )
+        .unwrap();
+
+        // When: The referenced files are determined.
+        let got = extension.references();
+
+        // Then: Only the paths within the project are returned.
+        assert_eq!(
+            got,
+            vec![
+                PathBuf::from("bin/libaddon.so"),
+                PathBuf::from("bin/libdep.so")
+            ]
+        );
+    }
+}
@@ -0,0 +1,190 @@
+use semver::Version;
+use std::collections::HashMap;
+
+use super::Configuration;
+use super::Parsable;
+use super::ParsableError;
+
+const PROJECT_GODOT_FILENAME: &str = "project.godot";
+
+/* -------------------------------------------------------------------------- */
+/*                           Struct: ProjectSettings                          */
+/* -------------------------------------------------------------------------- */
+
+/// `ProjectSettings` is a view of the properties defined within a _Godot_
+/// project's `project.godot` file.
+///
+/// NOTE: The `project.godot` format is only _mostly_ INI-compatible; values
+/// may span multiple lines (e.g. input maps), which is why it isn't parsed with
+/// [`ini::Ini`]. Only single-line properties are retained.
+#[derive(Clone, Debug, Default)]
+pub struct ProjectSettings(HashMap<String, HashMap<String, String>>);
+
+/* -------------------------- Impl: ProjectSettings ------------------------- */
+
+impl ProjectSettings {
+    /// `get` returns the raw value of the property `key` within `section`.
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.0
+            .get(section)
+            .and_then(|s| s.get(key))
+            .map(String::as_str)
+    }
+
+    /// `features` returns the list of features declared by the project (i.e.
+    /// the `application/config/features` property).
+    pub fn features(&self) -> Vec<String> {
+        let value = match self.get("application", "config/features") {
+            None => return vec![],
+            Some(v) => v,
+        };
+
+        let value = value
+            .trim()
+            .strip_prefix("PackedStringArray(")
+            .and_then(|s| s.strip_suffix(')'))
+            .unwrap_or(value);
+
+        value
+            .split(',')
+            .map(|s| s.trim().trim_matches('"'))
+            .filter(|s| !s.is_empty())
+            .map(str::to_owned)
+            .collect()
+    }
+
+    /// `version` returns the _Godot_ version the project was last saved with,
+    /// as recorded by its features. Only the major and minor components are
+    /// stored by the editor, so the patch component will always be `0`.
+    pub fn version(&self) -> Option<Version> {
+        self.features().iter().find_map(|f| parse_version(f))
+    }
+}
+
+/* --------------------------- Impl: Configuration -------------------------- */
+
+impl Configuration for ProjectSettings {
+    fn file_name<'a>() -> Option<&'a str> {
+        Some(PROJECT_GODOT_FILENAME)
+    }
+
+    fn matches(path: impl AsRef<std::path::Path>) -> bool {
+        path.as_ref()
+            .file_name()
+            .is_some_and(|s| s == PROJECT_GODOT_FILENAME)
+    }
+}
+
+/* ----------------------------- Impl: Parsable ----------------------------- */
+
+impl Parsable for ProjectSettings {
+    fn parse(contents: &str) -> Result<Self, ParsableError> {
+        let mut sections = HashMap::<String, HashMap<String, String>>::new();
+        let mut section = String::default();
+
+        for line in contents.lines() {
+            // Skip comments, blank lines, and any continuation of a multi-line
+            // value (which can't start with a valid property name).
+            if line.is_empty() || line.starts_with([';', '#', ' ', '\t', '"', '}', ']']) {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                section = name.to_owned();
+                continue;
+            }
+
+            if let Some((key, value)) = line.split_once('=') {
+                sections
+                    .entry(section.clone())
+                    .or_default()
+                    .insert(key.trim().to_owned(), value.trim().to_owned());
+            }
+        }
+
+        Ok(ProjectSettings(sections))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                           Function: parse_version                          */
+/* -------------------------------------------------------------------------- */
+
+/// `parse_version` leniently parses a _Godot_ engine version string, such as
+/// `4.2`, `4.2.1`, or `4.2.1.stable.official`, into a [`Version`]. Missing
+/// components default to `0`.
+pub fn parse_version(value: &str) -> Option<Version> {
+    let mut parts = value
+        .trim()
+        .trim_matches('"')
+        .split(['.', '-', '+'])
+        .map_while(|s| s.parse::<u64>().ok());
+
+    let major = parts.next()?;
+    let minor = parts.next()?;
+    let patch = parts.next().unwrap_or(0);
+
+    Some(Version::new(major, minor, patch))
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Mod: Tests                                 */
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use semver::Version;
+
+    use super::parse_version;
+    use super::Parsable;
+    use super::ProjectSettings;
+
+    /* ------------------------ Test: ProjectSettings ----------------------- */
+
+    #[test]
+    fn test_project_settings_version_skips_multiline_values() {
+        // Given: A 'project.godot' file with a multi-line input map.
+        let contents = r#"; Engine configuration file.
+config_version=5
+
+[application]
+
+config/name="Game"
+config/features=PackedStringArray("4.2", "Forward Plus")
+
+[input]
+
+ui_accept={
+"deadzone": 0.5,
+"events": []
+}
+"#;
+
+        // When: The file is parsed.
+        let got = ProjectSettings::parse(contents).unwrap();
+
+        // Then: The features and version are correctly read.
+        assert_eq!(got.features(), vec!["4.2", "Forward Plus"]);
+        assert_eq!(got.version(), Some(Version::new(4, 2, 0)));
+    }
+
+    /* ------------------------- Test: parse_version ------------------------ */
+
+    #[rstest]
+    #[case("4.2", Some(Version::new(4, 2, 0)))]
+    #[case("4.2.1", Some(Version::new(4, 2, 1)))]
+    #[case("4.2.1.stable.official", Some(Version::new(4, 2, 1)))]
+    #[case("4.3-rc1", Some(Version::new(4, 3, 0)))]
+    #[case("\"4.1\"\n", Some(Version::new(4, 1, 0)))]
+    #[case("4", None)]
+    #[case("Forward Plus", None)]
+    fn test_parse_version(#[case] input: &str, #[case] want: Option<Version>) {
+        // Given: A Godot version string.
+        // When: The string is parsed.
+        let got = parse_version(input);
+
+        // Then: The expected version is returned.
+        assert_eq!(got, want);
+    }
+}
@@ -296,12 +296,27 @@ mod tests {
         r#"{ rev = "rev", git = "https://github.com/" }"#
     );
 
+    test_ser_source!(
+        test_ser_source_repo_without_submodules,
+        Source::Git(
+            git::Source::builder()
+                .repo(
+                    url::Url::parse("https://github.com")
+                        .map(git::Remote::from)
+                        .unwrap()
+                )
+                .submodules(Some(false))
+                .build()
+        ),
+        r#"{ git = "https://github.com/", submodules = false }"#
+    );
+
     test_ser_source!(
         test_ser_source_repo_with_release,
         Source::Release(
             git::GitHubRelease::builder()
                 .tag(String::from("tag"))
-                .asset(String::from("asset"))
+                .asset(Some(String::from("asset")))
                 .repo(
                     url::Url::parse("https://github.com")
                         .map(git::Remote::from)
@@ -312,6 +327,22 @@ mod tests {
         r#"{ git = "https://github.com/", release = "tag", asset = "asset" }"#
     );
 
+    test_ser_source!(
+        test_ser_source_repo_with_pinned_release,
+        Source::Release(
+            git::GitHubRelease::builder()
+                .tag(String::from("tag"))
+                .sha256(Some(String::from("abc")))
+                .repo(
+                    url::Url::parse("https://github.com")
+                        .map(git::Remote::from)
+                        .unwrap()
+                )
+                .build()
+        ),
+        r#"{ git = "https://github.com/", release = "tag", sha256 = "abc" }"#
+    );
+
     #[test]
     fn test_dependency_serializes_with_attrs_to_table() -> Result<(), toml_edit::ser::Error> {
         let dep = Dependency::builder()
@@ -328,6 +359,23 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_dependency_serializes_with_features_to_table() -> Result<(), toml_edit::ser::Error> {
+        let dep = Dependency::builder()
+            .addon(Some(String::from("abc")))
+            .features(vec![String::from("l10n")])
+            .optional(true)
+            .source(PathBuf::from("a/b/c"))
+            .build();
+
+        assert_eq!(
+            dep.serialize(ValueSerializer::new())?.to_string(),
+            r#"{ optional = true, features = ["l10n"], path = "a/b/c" }"#
+        );
+
+        Ok(())
+    }
+
     /* ------------------------ Test: Deserialization ----------------------- */
 
     macro_rules! test_de_source {
@@ -409,6 +457,21 @@ mod tests {
         )
     );
 
+    test_de_source!(
+        test_de_source_repo_with_latest_release,
+        r#"{ git = "https://github.com", release = "latest" }"#,
+        Source::Release(
+            git::GitHubRelease::builder()
+                .repo(
+                    url::Url::parse("https://github.com")
+                        .map(git::Remote::from)
+                        .unwrap()
+                )
+                .tag(String::from("latest"))
+                .build()
+        )
+    );
+
     test_de_source!(
         test_de_source_repo_with_release,
         r#"{ git = "https://github.com", release = "tag", asset = "asset" }"#,
@@ -420,7 +483,43 @@ mod tests {
                         .unwrap()
                 )
                 .tag(String::from("tag"))
-                .asset(String::from("asset"))
+                .asset(Some(String::from("asset")))
+                .build()
+        )
+    );
+
+    test_de_source!(
+        test_de_source_repo_with_verify,
+        r#"{ git = "https://github.com", tag = "v1.0.0", verify = { gpg-keys = ["BE52490313633553255F49BA7AC00AC0B80A6FFD"], ssh-allowed-signers = ["keys"] } }"#,
+        Source::Git(
+            git::Source::builder()
+                .repo(
+                    url::Url::parse("https://github.com")
+                        .map(git::Remote::from)
+                        .unwrap()
+                )
+                .reference(Some(git::Reference::Tag(String::from("v1.0.0"))))
+                .verify(Some(git::Verify {
+                    gpg_keys: vec![String::from("BE52490313633553255F49BA7AC00AC0B80A6FFD")],
+                    ssh_allowed_signers: vec![PathBuf::from("keys")],
+                    minisign_keys: vec![],
+                }))
+                .build()
+        )
+    );
+
+    test_de_source!(
+        test_de_source_repo_with_pinned_release,
+        r#"{ git = "https://github.com", release = "tag", sha256 = "abc" }"#,
+        Source::Release(
+            git::GitHubRelease::builder()
+                .repo(
+                    url::Url::parse("https://github.com")
+                        .map(git::Remote::from)
+                        .unwrap()
+                )
+                .tag(String::from("tag"))
+                .sha256(Some(String::from("abc")))
                 .build()
         )
     );
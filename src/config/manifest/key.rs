@@ -4,6 +4,9 @@ use typed_builder::TypedBuilder;
 
 pub(super) const MANIFEST_SECTION_ADDONS: &str = "addons";
 pub(super) const MANIFEST_SECTION_ADDONS_DEV: &str = "dev-addons";
+pub(super) const MANIFEST_SECTION_FEATURES: &str = "features";
+pub(super) const MANIFEST_SECTION_PEER_ADDONS: &str = "peer-addons";
+pub(super) const MANIFEST_SECTION_PATCH: &str = "patch";
 pub(super) const MANIFEST_SECTION_TARGET: &str = "target";
 
 /* -------------------------------------------------------------------------- */
@@ -22,6 +22,7 @@ pub use project::Project;
 /* -------------------------------------------------------------------------- */
 
 use anyhow::anyhow;
+use semver::VersionReq;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::path::Path;
@@ -90,12 +91,12 @@ Gut = { git = "https://github.com/bitwes/Gut.git", tag = "v9.2.1" }
 
     /// Returns an _immutable_ view of the addons recorded for the provided
     /// [`Query`].
-    pub fn addons<'a>(&'a self, query: &'a Query) -> Addons {
+    pub fn addons<'a>(&'a self, query: &'a Query) -> Addons<'a> {
         Addons::builder().document(&self.0).query(query).build()
     }
 
     /// Returns a mutable view of the addons recorded for the provided [`Query`].
-    pub fn addons_mut<'a>(&'a mut self, query: &'a Query) -> AddonsMut {
+    pub fn addons_mut<'a>(&'a mut self, query: &'a Query) -> AddonsMut<'a> {
         AddonsMut::builder()
             .document(&mut self.0)
             .query(query)
@@ -118,14 +119,20 @@ Gut = { git = "https://github.com/bitwes/Gut.git", tag = "v9.2.1" }
     ///     2. The same addon cannot be replaced by 2+ addons. Note that because
     ///        replacements can only be specified within a target, any collision
     ///        is guaranteed to be an invalid state.
-    pub fn dependencies<'a>(
+    ///
+    /// NOTE: Optional dependencies are only included if they're enabled by one
+    /// of the specified `features`.
+    pub fn dependencies<'a, 'b>(
         &self,
         path: impl AsRef<Path>,
         is_dev: bool,
         targets: impl IntoIterator<Item = Option<&'a str>>,
+        features: impl IntoIterator<Item = &'b str>,
     ) -> Result<Vec<Dependency>, Error> {
         let mut out: Vec<(Query, Dependency)> = vec![];
 
+        let enabled = self.resolve_features(features)?;
+
         let mut targets = targets.into_iter().collect::<Vec<_>>();
         if !targets.contains(&None) {
             targets.push(None);
@@ -153,10 +160,17 @@ Gut = { git = "https://github.com/bitwes/Gut.git", tag = "v9.2.1" }
                 return Err(Error::MissingTarget);
             }
 
-            out.extend(self.addons(&query).into_iter().map(|mut d| {
-                let _ = d.included_from.insert(path.as_ref().to_owned());
-                (query.clone(), d)
-            }));
+            out.extend(
+                self.addons(&query)
+                    .into_iter()
+                    .filter(|d| {
+                        !d.optional || d.addon.as_ref().is_some_and(|a| enabled.contains(a))
+                    })
+                    .map(|mut d| {
+                        let _ = d.included_from.insert(path.as_ref().to_owned());
+                        (query.clone(), d)
+                    }),
+            );
         }
 
         Manifest::check_for_duplicate(&out)?;
@@ -178,14 +192,162 @@ Gut = { git = "https://github.com/bitwes/Gut.git", tag = "v9.2.1" }
         Ok(out.into_iter().map(|(_, d)| d).collect())
     }
 
+    /// Returns the [`Dependency`] overrides declared within the [`Manifest`]'s
+    /// root-level `patch` section, keyed by the name of the patched addon. A
+    /// patch redirects *every* occurrence of an addon within the dependency
+    /// graph to the patched source; the other properties of each occurrence
+    /// (e.g. its hooks) are kept, though the patch's features are enabled too.
+    ///
+    /// NOTE: A `path` to the directory containing the [`Manifest`] must be
+    /// provided so that a patch with a relative [`crate::core::Source::Path`]
+    /// can be properly resolved.
+    pub fn patches(&self, path: impl AsRef<Path>) -> Result<HashMap<String, Dependency>, Error> {
+        let mut out = HashMap::new();
+
+        let patches = match self
+            .0
+            .get(key::MANIFEST_SECTION_PATCH)
+            .and_then(|v| v.as_table_like())
+        {
+            None => return Ok(out),
+            Some(t) => t,
+        };
+
+        for (name, value) in patches.iter() {
+            let mut dep =
+                Dependency::try_from(value).map_err(|_| Error::InvalidPatch(name.to_owned()))?;
+
+            // NOTE: A patch always applies to the addon named by its key, so
+            // specifying a replacement is meaningless.
+            if dep.replace.is_some() {
+                return Err(Error::InvalidPatch(name.to_owned()));
+            }
+
+            dep.addon.replace(name.to_owned());
+            dep.included_from.replace(path.as_ref().to_owned());
+
+            out.insert(name.to_owned(), dep);
+        }
+
+        Ok(out)
+    }
+
+    /// Returns the peer addons declared within the [`Manifest`], mapping each
+    /// addon name to its required version. A peer addon must be provided by
+    /// the project installing this one; it will never be installed itself.
+    pub fn peers(&self) -> Result<HashMap<String, VersionReq>, Error> {
+        let mut out = HashMap::new();
+
+        let peers = match self
+            .0
+            .get(key::MANIFEST_SECTION_PEER_ADDONS)
+            .and_then(|v| v.as_table_like())
+        {
+            None => return Ok(out),
+            Some(t) => t,
+        };
+
+        for (name, value) in peers.iter() {
+            let req = value
+                .as_str()
+                .and_then(|s| VersionReq::parse(s).ok())
+                .ok_or(Error::InvalidPeer(name.to_owned()))?;
+
+            out.insert(name.to_owned(), req);
+        }
+
+        Ok(out)
+    }
+
+    /// Returns the _Godot_ engine version requirement declared within the
+    /// `project` table of the [`Manifest`] (e.g. `godot = ">=4.2, <4.4"`), if
+    /// any.
+    pub fn godot(&self) -> Result<Option<VersionReq>, Error> {
+        let value = match self
+            .0
+            .get(project::MANIFEST_SECTION_PROJECT)
+            .and_then(|v| v.get(project::MANIFEST_SECTION_PROJECT_GODOT))
+        {
+            None => return Ok(None),
+            Some(v) => v,
+        };
+
+        value
+            .as_str()
+            .and_then(|s| VersionReq::parse(s).ok())
+            .map(Some)
+            .ok_or(Error::InvalidGodot(value.to_string().trim().to_owned()))
+    }
+
     /// Returns an _immutable_ view of the project configuration within the
     /// [`Manifest`].
-    pub fn project(&self) -> Project {
+    pub fn project(&self) -> Project<'_> {
         Project::builder().document(&self.0).build()
     }
 
+    /// Returns the features declared within the [`Manifest`], mapping each
+    /// feature name to the list of optional addons and other features which it
+    /// enables.
+    pub fn features(&self) -> HashMap<String, Vec<String>> {
+        self.0
+            .get(key::MANIFEST_SECTION_FEATURES)
+            .and_then(|v| v.as_table_like())
+            .map(|t| {
+                t.iter()
+                    .map(|(k, v)| {
+                        let enables = v
+                            .as_array()
+                            .map(|a| a.iter().filter_map(|v| v.as_str()))
+                            .into_iter()
+                            .flatten()
+                            .map(str::to_owned)
+                            .collect::<Vec<_>>();
+
+                        (k.to_owned(), enables)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
     /* -------------------------- Methods: Private -------------------------- */
 
+    /// `resolve_features` expands the provided `features` into the set of
+    /// optional addon names which they enable. A feature may enable other
+    /// features, in which case they're expanded recursively.
+    fn resolve_features<'a>(
+        &self,
+        features: impl IntoIterator<Item = &'a str>,
+    ) -> Result<HashSet<String>, Error> {
+        let declared = self.features();
+
+        let mut out = HashSet::new();
+        let mut visited = HashSet::new();
+
+        let mut to_visit = features.into_iter().map(str::to_owned).collect::<Vec<_>>();
+
+        while let Some(feature) = to_visit.pop() {
+            if !visited.insert(feature.clone()) {
+                continue;
+            }
+
+            let enables = declared
+                .get(&feature)
+                .ok_or(Error::UnknownFeature(feature.clone()))?;
+
+            for name in enables {
+                match declared.contains_key(name) {
+                    true => to_visit.push(name.to_owned()),
+                    false => {
+                        out.insert(name.to_owned());
+                    }
+                }
+            }
+        }
+
+        Ok(out)
+    }
+
     /// `check_for_duplicate` validates that the provided [`Dependency`] list
     /// does not contain duplicate specifications of an [`crate::core::Addon`].
     fn check_for_duplicate(deps: &[(Query, Dependency)]) -> Result<(), Error> {
@@ -432,6 +594,14 @@ pub enum Error {
     DoubleReplace(String, Vec<String>),
     #[error("cannot specify replacement without a target: {0}")]
     InvalidReplace(String),
+    #[error("invalid Godot version requirement: {0}")]
+    InvalidGodot(String),
+    #[error("invalid patch for addon: {0}")]
+    InvalidPatch(String),
+    #[error("invalid version requirement for peer addon: {0}")]
+    InvalidPeer(String),
+    #[error("unknown feature: {0}")]
+    UnknownFeature(String),
 }
 
 /* -------------------------------------------------------------------------- */
@@ -439,4 +609,73 @@ pub enum Error {
 /* -------------------------------------------------------------------------- */
 
 #[cfg(test)]
-mod tests {}
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::config::Parsable;
+
+    use super::Error;
+    use super::Manifest;
+
+    /* ---------------------- Test: Manifest::features ---------------------- */
+
+    macro_rules! dependency_names {
+        ($manifest:expr, $features:expr$(,)?) => {{
+            let mut names = $manifest
+                .dependencies(PathBuf::from("."), false, [None], $features)
+                .unwrap()
+                .into_iter()
+                .filter_map(|d| d.addon)
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+        }};
+    }
+
+    #[test]
+    fn test_manifest_dependencies_excludes_optional_without_features() {
+        let m = Manifest::parse(
+            r#"
+[addons]
+a = { path = "a" }
+b = { path = "b", optional = true }
+
+[features]
+b = ["b"]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(dependency_names!(m, []), vec!["a"]);
+    }
+
+    #[test]
+    fn test_manifest_dependencies_includes_optional_with_features() {
+        let m = Manifest::parse(
+            r#"
+[addons]
+a = { path = "a" }
+b = { path = "b", optional = true }
+c = { path = "c", optional = true }
+
+[features]
+full = ["l10n", "c"]
+l10n = ["b"]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(dependency_names!(m, ["l10n"]), vec!["a", "b"]);
+        assert_eq!(dependency_names!(m, ["full"]), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_manifest_dependencies_fails_with_unknown_feature() {
+        let m = Manifest::parse("[addons]\na = { path = \"a\" }").unwrap();
+
+        assert!(matches!(
+            m.dependencies(PathBuf::from("."), false, [None], ["missing"]),
+            Err(Error::UnknownFeature(f)) if f == "missing",
+        ));
+    }
+}
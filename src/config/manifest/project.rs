@@ -5,7 +5,10 @@ use crate::core::ExportFiles;
 use crate::core::ScriptTemplates;
 
 pub(super) const MANIFEST_SECTION_PROJECT: &str = "project";
+pub(super) const MANIFEST_SECTION_PROJECT_CONFLICTS: &str = "conflicts";
 pub(super) const MANIFEST_SECTION_PROJECT_EXPORT_FILES: &str = "export_files";
+pub(super) const MANIFEST_SECTION_PROJECT_GODOT: &str = "godot";
+pub(super) const MANIFEST_SECTION_PROJECT_PROVIDES: &str = "provides";
 pub(super) const MANIFEST_SECTION_PROJECT_SCRIPT_TEMPLATES: &str = "script_templates";
 
 /* -------------------------------------------------------------------------- */
@@ -22,6 +25,13 @@ pub struct Project<'a> {
 /* ------------------------------ Impl: Project ----------------------------- */
 
 impl<'a> Project<'a> {
+    /// `get_conflicts` returns the names of addons which cannot be installed
+    /// alongside this one, as declared within the `project` table of the
+    /// [`super::Manifest`].
+    pub fn get_conflicts(&self) -> Vec<String> {
+        self.get_names(MANIFEST_SECTION_PROJECT_CONFLICTS)
+    }
+
     /// `get_export_files` returns the exported file-related configuration
     /// within the `project` table of the [`super::Manifest`].
     pub fn get_export_files(&self) -> Option<ExportFiles> {
@@ -35,6 +45,16 @@ impl<'a> Project<'a> {
             .and_then(|t| ExportFiles::try_from(&toml_edit::value(t)).ok())
     }
 
+    /// `get_provides` returns the (possibly virtual) addon names which this
+    /// addon satisfies, as declared within the `project` table of the
+    /// [`super::Manifest`].
+    ///
+    /// NOTE: Provided names carry no version, so a dependency on one of them is
+    /// satisfied by this addon regardless of the version it requires.
+    pub fn get_provides(&self) -> Vec<String> {
+        self.get_names(MANIFEST_SECTION_PROJECT_PROVIDES)
+    }
+
     /// `get_script_templates` returns the script template-related configuration
     /// within the `project` table of the [`super::Manifest`].
     pub fn get_script_templates(&self) -> Option<ScriptTemplates> {
@@ -47,6 +67,23 @@ impl<'a> Project<'a> {
             .map(|t| t.clone().into_inline_table())
             .and_then(|t| ScriptTemplates::try_from(&toml_edit::value(t)).ok())
     }
+
+    /* -------------------------- Methods: Private -------------------------- */
+
+    /// `get_names` returns the list of addon names stored under `key` within
+    /// the `project` table of the [`super::Manifest`].
+    fn get_names(&self, key: &str) -> Vec<String> {
+        self.document
+            .as_table()
+            .get(MANIFEST_SECTION_PROJECT)
+            .and_then(|v| v.as_table())
+            .and_then(|t| t.get(key))
+            .and_then(|v| v.as_array())
+            .map(|a| a.iter().filter_map(|v| v.as_str()).map(str::to_owned))
+            .into_iter()
+            .flatten()
+            .collect()
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -2,6 +2,10 @@
 
 pub mod gdext;
 
+/* ------------------------------- Mod: godot ------------------------------- */
+
+pub mod godot;
+
 /* ------------------------------ Mod: manifest ----------------------------- */
 
 pub mod manifest;
@@ -13,6 +17,10 @@ use typed_builder::TypedBuilder;
 
 pub mod plugin;
 
+/* ------------------------------ Mod: settings ----------------------------- */
+
+pub mod settings;
+
 /* -------------------------------------------------------------------------- */
 /*                            Trait: Configuration                            */
 /* -------------------------------------------------------------------------- */
@@ -146,7 +154,7 @@ where
     #[builder(default = 1, setter(into))]
     min_depth: usize,
 
-    #[builder(default = std::usize::MAX, setter(into))]
+    #[builder(default = usize::MAX, setter(into))]
     max_depth: usize,
 
     #[builder(default)]
@@ -0,0 +1,292 @@
+use anyhow::anyhow;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use url::Url;
+
+use super::Configuration;
+use super::Parsable;
+use super::ParsableError;
+use crate::git::Verify;
+
+const SETTINGS_DEFAULT_HOST: &str = "github.com";
+const SETTINGS_FILENAME: &str = "config.toml";
+
+/* -------------------------------------------------------------------------- */
+/*                              Struct: Settings                              */
+/* -------------------------------------------------------------------------- */
+
+/// `Settings` contains user-specific `gdpack` configuration, which is read from
+/// the `config.toml` file within the `gdpack` home directory (i.e. the
+/// `GDPACK_HOME` environment variable). Unlike the [`super::Manifest`], these
+/// settings are never shared with dependents.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Settings {
+    /// The git host used to resolve `owner/repo` shorthand sources; defaults
+    /// to `github.com`.
+    pub default_host: Option<String>,
+    /// Per-host settings, keyed by the host name (e.g. `github.com`).
+    #[serde(default)]
+    pub hosts: HashMap<String, HostSettings>,
+    /// URL rewrites (similar to git's `url.<base>.insteadOf`) used to fetch
+    /// git repositories and release assets from mirrors.
+    #[serde(default)]
+    pub mirrors: Vec<Mirror>,
+    /// Network settings shared by git fetches and release downloads.
+    #[serde(default)]
+    pub network: NetworkSettings,
+    /// Keys trusted to sign every dependency which enables verification;
+    /// relative paths are resolved against the `gdpack` home directory.
+    #[serde(default)]
+    pub verify: Verify,
+}
+
+/* ----------------------------- Impl: Settings ----------------------------- */
+
+impl Settings {
+    /// `default_host` returns the git host used to resolve `owner/repo`
+    /// shorthand sources.
+    pub fn default_host(&self) -> &str {
+        self.default_host
+            .as_deref()
+            .unwrap_or(SETTINGS_DEFAULT_HOST)
+    }
+
+    /// `host` returns the settings for the provided `host`, if any are defined.
+    pub fn host(&self, host: &str) -> Option<&HostSettings> {
+        self.hosts.get(host)
+    }
+
+    /// `rewrite` returns the URLs from which `url` should be fetched, in order.
+    /// If a [Mirror] matches `url` (the longest `instead-of` prefix wins), then
+    /// each of its URLs is tried before `url` itself.
+    pub fn rewrite(&self, url: &Url) -> Vec<Url> {
+        let original = url.as_str();
+
+        let mirror = self
+            .mirrors
+            .iter()
+            .filter(|m| !m.instead_of.is_empty() && original.starts_with(&m.instead_of))
+            .max_by_key(|m| m.instead_of.len());
+
+        let mut urls = mirror
+            .map(|m| {
+                m.urls
+                    .iter()
+                    .filter_map(|u| Url::parse(&original.replacen(&m.instead_of, u, 1)).ok())
+                    .collect::<Vec<_>>()
+            })
+            .unwrap_or_default();
+
+        urls.push(url.clone());
+
+        urls
+    }
+}
+
+/* --------------------------- Impl: Configuration -------------------------- */
+
+impl Configuration for Settings {
+    fn file_name<'a>() -> Option<&'a str> {
+        Some(SETTINGS_FILENAME)
+    }
+
+    fn matches(path: impl AsRef<std::path::Path>) -> bool {
+        path.as_ref()
+            .file_name()
+            .is_some_and(|s| s == SETTINGS_FILENAME)
+    }
+}
+
+/* ----------------------------- Impl: Parsable ----------------------------- */
+
+impl Parsable for Settings {
+    fn parse(contents: &str) -> Result<Self, ParsableError> {
+        toml::from_str(contents).map_err(|e| ParsableError::Parse(anyhow!(e)))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                            Struct: HostSettings                            */
+/* -------------------------------------------------------------------------- */
+
+/// `HostSettings` contains the credentials and endpoints used when connecting
+/// to a specific git host.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct HostSettings {
+    /// An access token used for HTTP(S) authentication.
+    pub token: Option<String>,
+    /// The username to authenticate as; defaults to the username in the
+    /// repository URL.
+    pub username: Option<String>,
+    /// A path to a private SSH key used instead of the SSH agent.
+    pub ssh_key: Option<PathBuf>,
+    /// The base URL of the host's GitHub-compatible releases API; defaults to
+    /// `https://api.github.com` for `github.com` and `https://$HOST/api/v3`
+    /// otherwise.
+    pub api_url: Option<Url>,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                           Struct: NetworkSettings                          */
+/* -------------------------------------------------------------------------- */
+
+/// `NetworkSettings` configures how `gdpack` connects to remote hosts, both
+/// for git fetches and for HTTP(S) downloads (e.g. release assets).
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct NetworkSettings {
+    /// The proxy used for all connections; defaults to the `http_proxy` and
+    /// `https_proxy` environment variables (and git's `http.proxy` setting).
+    pub proxy: Option<Url>,
+    /// Paths to PEM-encoded certificate bundles which are trusted in addition
+    /// to the system's root certificates.
+    #[serde(default)]
+    pub ca_bundles: Vec<PathBuf>,
+    /// The maximum number of seconds to wait while connecting to a host. This
+    /// isn't applied to git fetches, which use `libgit2`'s default.
+    pub connect_timeout: Option<u64>,
+    /// The maximum number of seconds to wait for a host to respond. This isn't
+    /// applied to git fetches, which use `libgit2`'s default.
+    pub read_timeout: Option<u64>,
+    /// The number of times a request is retried after a transient failure
+    /// (e.g. a timeout, a dropped connection, or a `5xx` response).
+    pub retries: Option<u32>,
+    /// The `User-Agent` sent with each HTTP request (but not git fetches, which
+    /// use `libgit2`'s); defaults to `gdpack/$VERSION`.
+    pub user_agent: Option<String>,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                               Struct: Mirror                               */
+/* -------------------------------------------------------------------------- */
+
+/// `Mirror` rewrites URLs beginning with `instead_of` to begin with each of
+/// `urls` instead, which are tried in order before the original URL.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct Mirror {
+    /// The URL prefix to rewrite (e.g. `https://github.com/`).
+    pub instead_of: String,
+    /// The replacement URL prefixes, in order of preference.
+    pub urls: Vec<String>,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Mod: Tests                                 */
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use url::Url;
+
+    use super::HostSettings;
+    use super::NetworkSettings;
+    use super::Parsable;
+    use super::Settings;
+
+    /* --------------------------- Test: Settings --------------------------- */
+
+    #[test]
+    fn test_settings_parse_host_credentials() {
+        // Given: A settings file with credentials for a host.
+        let contents = r#"
+[hosts."github.com"]
+token = "abc"
+ssh-key = "~/.ssh/id_ed25519"
+"#;
+
+        // When: The file is parsed.
+        let got = Settings::parse(contents).unwrap();
+
+        // Then: The host's credentials are read.
+        assert_eq!(
+            got.host("github.com"),
+            Some(&HostSettings {
+                token: Some("abc".to_owned()),
+                username: None,
+                ssh_key: Some(PathBuf::from("~/.ssh/id_ed25519")),
+                api_url: None,
+            })
+        );
+        assert_eq!(got.host("gitlab.com"), None);
+    }
+
+    /* ----------------------- Test: Settings::rewrite ---------------------- */
+
+    #[test]
+    fn test_settings_rewrite_prefers_longest_mirror() {
+        // Given: Mirrors for a host and for one of its namespaces.
+        let contents = r#"
+[[mirrors]]
+instead-of = "https://github.com/"
+urls = ["https://mirror.internal/github/"]
+
+[[mirrors]]
+instead-of = "https://github.com/owner/"
+urls = ["https://a.internal/owner/", "https://b.internal/owner/"]
+"#;
+
+        let settings = Settings::parse(contents).unwrap();
+
+        // When: A URL within the namespace is rewritten.
+        let url = Url::parse("https://github.com/owner/repo").unwrap();
+        let got = settings.rewrite(&url);
+
+        // Then: The namespace's mirrors are tried in order, then the original.
+        assert_eq!(
+            got.iter().map(Url::as_str).collect::<Vec<_>>(),
+            vec![
+                "https://a.internal/owner/repo",
+                "https://b.internal/owner/repo",
+                "https://github.com/owner/repo",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_settings_rewrite_without_mirror_is_original() {
+        // Given: Default settings without any mirrors.
+        let settings = Settings::default();
+
+        // When: A URL is rewritten.
+        let url = Url::parse("https://gitlab.com/owner/repo").unwrap();
+        let got = settings.rewrite(&url);
+
+        // Then: Only the original URL is used.
+        assert_eq!(got, vec![url]);
+    }
+
+    /* ------------------------ Test: NetworkSettings ----------------------- */
+
+    #[test]
+    fn test_settings_parse_network() {
+        // Given: A settings file with network settings.
+        let contents = r#"
+[network]
+proxy = "http://proxy.internal:3128"
+ca-bundles = ["~/certs/root.pem"]
+connect-timeout = 10
+retries = 5
+"#;
+
+        // When: The file is parsed.
+        let got = Settings::parse(contents).unwrap();
+
+        // Then: The network settings are read, with defaults for the rest.
+        assert_eq!(
+            got.network,
+            NetworkSettings {
+                proxy: Some(Url::parse("http://proxy.internal:3128").unwrap()),
+                ca_bundles: vec![PathBuf::from("~/certs/root.pem")],
+                connect_timeout: Some(10),
+                read_timeout: None,
+                retries: Some(5),
+                user_agent: None,
+            }
+        );
+    }
+}
@@ -5,6 +5,7 @@ use std::path::PathBuf;
 use typed_builder::TypedBuilder;
 
 use crate::config::gdext::Extension;
+use crate::config::godot::ProjectSettings;
 use crate::config::manifest::Manifest;
 use crate::config::plugin::Plugin;
 use crate::config::Configuration;
@@ -219,6 +220,29 @@ impl Addon {
             .version(version)
             .build())
     }
+
+    /* -------------------------- Methods: Private -------------------------- */
+
+    /// `get_referenced_files` returns the paths of the files referenced by the
+    /// [`Addon`]'s extensions. These are resolved against the _Godot_ project
+    /// containing the [`Addon`], or `root` if it isn't within one.
+    fn get_referenced_files(&self, root: impl AsRef<Path>) -> Vec<PathBuf> {
+        let path_project = self
+            .path
+            .ancestors()
+            .take_while(|p| p.starts_with(root.as_ref()))
+            .find(|p| p.join(ProjectSettings::file_name().unwrap()).is_file())
+            .unwrap_or(root.as_ref());
+
+        FileQuery::<Extension>::builder()
+            .path(self.path.as_path())
+            .build()
+            .into_iter()
+            .filter_map(|(_, e)| e.ok())
+            .flat_map(|e| e.references())
+            .map(|p| path_project.join(p))
+            .collect()
+    }
 }
 
 /* ---------------------------- Impl: Installable --------------------------- */
@@ -357,7 +381,16 @@ impl TryFrom<&Dependency> for Addon {
             .as_ref()
             .ok_or(anyhow!("cannot determine addon name"))?;
 
-        let mut addon = Addon::find_in_dir(root, name)?;
+        let mut addon = Addon::find_in_dir(&root, name)?;
+
+        // A 'git' checkout only contains the files needed to discover addons;
+        // write the rest of the discovered addon's files, followed by any files
+        // outside of it which its extensions reference (e.g. libraries).
+        crate::git::include(&addon.path)?;
+
+        for path in addon.get_referenced_files(&root) {
+            crate::git::include(path)?;
+        }
 
         if let Some(s) = value.hooks.pre.as_deref() {
             let _ = addon.hooks.pre.insert(s.to_owned());
@@ -370,9 +403,10 @@ impl TryFrom<&Dependency> for Addon {
         // from a 'git' tag.
         if addon.version.is_none() {
             match &value.source {
-                super::Source::Release(r) => {
-                    let tag = r.tag.as_str().strip_prefix('v').unwrap_or(&r.tag);
-                    if let Ok(v) = semver::Version::parse(tag) {
+                // NOTE: A release may have been resolved from `latest` or a
+                // version requirement, so use the tag it was resolved to.
+                super::Source::Release(_) => {
+                    if let Some(v) = crate::git::GitHubRelease::get_version(&root) {
                         addon.version.replace(v);
                     }
                 }
@@ -471,6 +505,29 @@ mod tests {
         };
     }
 
+    /* ------------------ Test: Addon::get_referenced_files ----------------- */
+
+    #[test]
+    fn test_addon_get_referenced_files_outside_addon() {
+        let tmp = tempdir().expect("failed to make temporary directory");
+
+        // Given: A project containing an extension which references a library
+        // outside of its addon directory.
+        write_file!(&tmp.path().join("project.godot"), "");
+        write_file!(
+            &tmp.path().join("addons/addon/addon.gdextension"),
+            "[libraries]\nlinux.x86_64 = \"res://bin/libaddon.so\"",
+        );
+
+        let addon = Addon::find_in_dir(tmp.path(), "addon").expect("couldn't find addon");
+
+        // When: The files referenced by the addon are determined.
+        let got = addon.get_referenced_files(tmp.path());
+
+        // Then: The library is resolved relative to the project root.
+        assert_eq!(got, vec![tmp.path().join("bin/libaddon.so")]);
+    }
+
     /* ---------------------- Test: Addon::find_in_dir ---------------------- */
 
     #[test]
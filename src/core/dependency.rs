@@ -74,6 +74,24 @@ pub struct Dependency {
     #[builder(default)]
     #[serde(skip)]
     pub is_direct: bool,
+    /// Whether the [`Dependency`]'s source was overridden by a patch declared
+    /// in the project's manifest. Used to allow insecure paths when installing.
+    ///
+    /// NOTE: This must be manually set during dependency resolution as it's
+    /// dependent on install-time context. As such, it should not be serialized.
+    #[builder(default)]
+    #[serde(skip)]
+    pub is_patched: bool,
+    /// Whether the [`Dependency`] is only installed when one of the features
+    /// declared by its [`super::Manifest`] requests it.
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    /// A list of features to enable within the addon's own manifest. Each
+    /// feature enables a set of optional dependencies of the addon.
+    #[builder(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
     /// Name of an addon to replace during installation.
     ///
     /// NOTE: This value will not be propagated to consumers of this project.
@@ -99,7 +117,12 @@ impl Dependency {
     /// [`Dependency`] is already downloaded.
     pub fn download(&self) -> Result<PathBuf, Error> {
         let path = match &self.source {
-            Source::Git(s) => crate::git::checkout(s).map(|c| c.path).map_err(Error::Git),
+            Source::Git(s) => {
+                let mut s = s.clone();
+                s.verify = self.get_rooted_verify(s.verify.as_ref());
+
+                crate::git::checkout(&s).map(|c| c.path).map_err(Error::Git)
+            }
             Source::Path { path } => self
                 .included_from
                 .as_ref()
@@ -108,7 +131,7 @@ impl Dependency {
                     |path_root| match Dependency::get_rooted_path(path_root, path) {
                         Ok(p) => Ok(p),
                         Err(Error::InsecurePath(p)) => {
-                            if self.is_direct {
+                            if self.is_direct || self.is_patched {
                                 return Ok(p);
                             }
 
@@ -117,10 +140,12 @@ impl Dependency {
                         Err(e) => Err(e),
                     },
                 ),
-            Source::Release(release) => release
-                .download()
-                .and_then(|_| release.get_path())
-                .map_err(Error::Git),
+            Source::Release(release) => {
+                let mut release = release.clone();
+                release.verify = self.get_rooted_verify(release.verify.as_ref());
+
+                release.download().map_err(Error::Git)
+            }
         }?;
 
         if !path.is_dir() {
@@ -148,6 +173,16 @@ impl Dependency {
 
     /* -------------------------- Methods: Private -------------------------- */
 
+    /// `get_rooted_verify` resolves the relative paths of the signature
+    /// verification settings `verify` against the directory which included
+    /// this [`Dependency`].
+    fn get_rooted_verify(&self, verify: Option<&git::Verify>) -> Option<git::Verify> {
+        match (verify, self.included_from.as_ref()) {
+            (Some(v), Some(path)) => Some(v.rooted_at(path)),
+            (v, _) => v.cloned(),
+        }
+    }
+
     /// `get_rooted_path` is a convenience function for turning a potentially
     /// relative path (`path`) into one joined onto `path_root`. Note that if
     /// `path` is absolute then it must be prefixed by `path_root`, otherwise
@@ -226,6 +261,21 @@ impl Source {
     }
 }
 
+/* ------------------------------ Impl: Display ----------------------------- */
+
+impl std::fmt::Display for Source {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Source::Release(r) => write!(f, "{} (release: {})", r.repo, r.tag),
+            Source::Git(g) => match g.reference.as_ref() {
+                None => write!(f, "{}", g.repo),
+                Some(r) => write!(f, "{} ({})", g.repo, r),
+            },
+            Source::Path { path } => write!(f, "{}", path.display()),
+        }
+    }
+}
+
 /* ------------------------- Impl: Into<Dependency> ------------------------- */
 
 impl From<&Source> for Dependency {
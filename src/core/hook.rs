@@ -40,10 +40,10 @@ impl Hook {
         if let Some(script) = self.pre.as_ref() {
             let status = Hook::run(script, path_working_dir)?;
             if !status.success() {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    format!("command failed with status {:?}", status.code()),
-                ));
+                return Err(std::io::Error::other(format!(
+                    "command failed with status {:?}",
+                    status.code()
+                )));
             }
         }
 
@@ -55,10 +55,10 @@ impl Hook {
         if let Some(script) = self.post.as_ref() {
             let status = Hook::run(script, path_working_dir)?;
             if !status.success() {
-                return Err(std::io::Error::new(
-                    std::io::ErrorKind::Other,
-                    format!("command failed with status {:?}", status.code()),
-                ));
+                return Err(std::io::Error::other(format!(
+                    "command failed with status {:?}",
+                    status.code()
+                )));
             }
         }
 
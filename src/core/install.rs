@@ -1,4 +1,6 @@
 use semver::Version;
+use semver::VersionReq;
+use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
@@ -6,12 +8,23 @@ use std::path::Path;
 use std::path::PathBuf;
 use typed_builder::TypedBuilder;
 
+use crate::config::gdext::Extension;
+use crate::config::godot;
+use crate::config::godot::ProjectSettings;
+use crate::config::Configuration;
+use crate::config::FileQuery;
 use crate::config::Manifest;
+use crate::config::Parsable;
+use crate::config::ParsableError;
+use crate::git::Reference;
 
 use super::Addon;
 use super::Dependency;
 use super::Installable;
 use super::ScriptTemplateScan;
+use super::Source;
+
+const GODOT_VERSION_FILENAME: &str = ".godot-version";
 
 /* -------------------------------------------------------------------------- */
 /*                               Struct: Install                              */
@@ -19,13 +32,37 @@ use super::ScriptTemplateScan;
 
 #[derive(Debug, TypedBuilder)]
 pub struct Install<'a> {
+    /// Whether to only warn, rather than fail, when an addon's peer addon is
+    /// not part of the resolved set of addons.
+    #[builder(default)]
+    pub allow_missing_peers: bool,
     #[builder(default = true)]
     pub dev: bool,
+    /// The features to enable within the project's own [`Manifest`].
+    #[builder(default)]
+    pub features: Vec<&'a str>,
     pub manifest: &'a Manifest,
+    /// The strategy used to select between compatible versions of an addon.
+    #[builder(default)]
+    pub resolution: Resolution,
     #[builder(default)]
     pub targets: Vec<Option<&'a str>>,
 }
 
+/* ---------------------------- Enum: Resolution ---------------------------- */
+
+/// `Resolution` defines which version is selected when multiple compatible
+/// versions of the same addon are requested within the dependency graph.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, clap::ValueEnum)]
+pub enum Resolution {
+    /// Select the highest compatible version.
+    #[default]
+    Highest,
+    /// Select the lowest compatible version; useful for verifying that the
+    /// lower bounds of an addon's dependencies are correct.
+    Lowest,
+}
+
 /* ------------------------------ Impl: Install ----------------------------- */
 
 impl<'a> Install<'a> {
@@ -129,23 +166,102 @@ impl<'a> Install<'a> {
     /// `resolve_addons` determines the set of [`Addon`] dependencies to install
     /// into the project specified by `path`.
     fn resolve_addons(&self, path: impl AsRef<Path>) -> Result<Vec<Addon>, Error> {
-        let mut to_install: HashMap<String, (Addon, Dependency)> = HashMap::new(); // addon name -> ...
+        let path = path.as_ref();
+
+        // NOTE: Only patches declared by the root manifest are considered; any
+        // declared by transitive dependencies are not propagated.
+        let patches = self.manifest.patches(path).map_err(Error::Config)?;
+        let mut patched = HashSet::<String>::new();
+
+        let mut to_install: HashMap<String, (Addon, Dependency, Chain)> = HashMap::new(); // addon name -> ...
         let mut subfolders = HashMap::<String, String>::new(); // subfolder name -> addon name
+        let mut features = HashMap::<String, BTreeSet<String>>::new(); // addon name -> features
+        let mut requests = HashMap::<String, HashMap<String, BTreeSet<String>>>::new(); // addon name -> requester name -> features
+        let mut conflicts = HashMap::<String, String>::new(); // conflicting name -> addon name
+        let mut provided = HashMap::<String, String>::new(); // provided name -> addon name
+        let mut skipped = Vec::<(Dependency, Chain)>::new(); // dependencies satisfied by a provider
+
+        // Addons which supersede another selected addon (provided name -> (addon
+        // name, superseded chain, providing chain)).
+        let mut superseded = HashMap::<String, (String, Chain, Chain)>::new();
 
         let mut to_visit = self
             .manifest
-            .dependencies(path, self.dev, self.targets.iter().copied())
-            .map(|mut deps| {
-                for d in deps.iter_mut() {
-                    d.is_direct = true;
-                }
-
-                deps
+            .dependencies(
+                path,
+                self.dev,
+                self.targets.iter().copied(),
+                self.features.iter().copied(),
+            )
+            .map(|deps| {
+                deps.into_iter()
+                    .map(|mut d| {
+                        d.is_direct = true;
+                        (d, Chain::default())
+                    })
+                    .collect::<Vec<_>>()
             })
             .map(VecDeque::from)
             .map_err(Error::Config)?;
 
-        'dep: while let Some(dep) = to_visit.pop_front() {
+        // Record the names required directly by the project; an addon which
+        // provides one of them can't be installed, regardless of the order in
+        // which they're visited.
+        let direct = to_visit
+            .iter()
+            .filter_map(|(d, _)| d.addon.clone())
+            .collect::<HashSet<_>>();
+
+        'dep: while let Some((mut dep, parent)) = to_visit.pop_front() {
+            // Redirect the [`Dependency`] to its patched source, if any. Only
+            // the source (and any features) of the patch is applied, so the
+            // rest of the [`Dependency`] (e.g. its hooks) is kept. Note that
+            // a patch is rooted at the project, so it may point outside of
+            // the project directory like a direct dependency.
+            if let Some(patch) = dep.addon.as_ref().and_then(|n| patches.get(n)) {
+                if patched.insert(patch.addon.clone().unwrap_or_default()) {
+                    println!(
+                        "patching addon '{}' with: {}",
+                        patch.addon.as_deref().unwrap_or("unknown"),
+                        patch.source,
+                    );
+                }
+
+                dep.source = patch.source.clone();
+                dep.included_from = patch.included_from.clone();
+                dep.is_patched = true;
+                dep.features.extend(patch.features.iter().cloned());
+            }
+
+            // Skip any [`Dependency`] which is already satisfied by an addon
+            // providing it, and reject any which a selected addon conflicts
+            // with. Both checks happen before downloading the [`Dependency`].
+            //
+            // NOTE: A provided name is virtual, so any version requirement on
+            // it can't be checked; the providing addon satisfies all of them.
+            if let Some(name) = dep.addon.as_ref() {
+                if provided.get(name).is_some_and(|p| p != name) {
+                    skipped.push((dep, parent));
+                    continue 'dep;
+                }
+
+                if let Some(declared_by) = conflicts.get(name).filter(|n| *n != name) {
+                    let chain_other = parent.join(Link {
+                        name: name.to_owned(),
+                        requirement: None,
+                    });
+
+                    return Err(Error::Conflicting(Conflict::conflicting(
+                        name,
+                        to_install
+                            .get(declared_by)
+                            .map(|(_, _, c)| c.clone())
+                            .unwrap_or_default(),
+                        chain_other,
+                    )));
+                }
+            }
+
             // Download the [`Dependency`] to the `gdpack` store.
             let path_dep = dep.download().map_err(Error::Dependency)?;
 
@@ -158,13 +274,69 @@ impl<'a> Install<'a> {
                 .map(String::to_owned)
                 .ok_or(Error::MissingName)?;
 
+            // Record how this [`Addon`] was reached from the root manifest so
+            // that any conflicts can be explained to the user.
+            let chain = parent.join(Link::new(&name, &dep));
+
+            // Enable any features requested by this [`Dependency`]. If the addon
+            // was already selected, then queue the optional dependencies which
+            // are enabled by the newly-requested features.
+            let enabled = features.entry(name.clone()).or_default();
+            let enabled_prev = enabled.clone();
+
+            enabled.extend(dep.features.iter().cloned());
+
+            // Record which addon requested this one so that the request may be
+            // withdrawn if the requesting addon is later superseded.
+            requests
+                .entry(name.clone())
+                .or_default()
+                .entry(parent.last().map(|l| l.name.clone()).unwrap_or_default())
+                .or_default()
+                .extend(dep.features.iter().cloned());
+
+            if let Some((existing, dep_prev, chain_prev)) = to_install.get(&name) {
+                if let Some(m) = existing.manifest.as_ref() {
+                    if enabled.len() > enabled_prev.len() {
+                        let path_prev = dep_prev.download().map_err(Error::Dependency)?;
+
+                        let targets = self.targets.iter().copied();
+                        let prev = m
+                            .dependencies(
+                                &path_prev,
+                                false,
+                                targets,
+                                enabled_prev.iter().map(String::as_str),
+                            )
+                            .map_err(Error::Config)?;
+
+                        let targets = self.targets.iter().copied();
+                        let next = m
+                            .dependencies(
+                                &path_prev,
+                                false,
+                                targets,
+                                enabled.iter().map(String::as_str),
+                            )
+                            .map_err(Error::Config)?;
+
+                        to_visit.extend(
+                            next.into_iter()
+                                .filter(|d| !prev.iter().any(|p| p.addon == d.addon))
+                                .map(|d| (d, chain_prev.clone())),
+                        );
+                    }
+                }
+            }
+
             // Validate the following invariants of the dependency set:
 
             // 1. The same plugin cannot be included more than once. If multiple
             //    major-compatible versions are included, then the highest
             //    version will be selected (under the assumption that semantic
-            //    version rules are upheld). If competing major versions are
-            //    found, an error will be returned.
+            //    version rules are upheld), unless the lowest version was
+            //    requested via [`Resolution::Lowest`]. If competing major
+            //    versions are found, an error will be returned.
             //
             //    NOTE: For non-plugins, there isn't a canonical way to
             //    determine the version of the dependency - it depends on the
@@ -173,49 +345,63 @@ impl<'a> Install<'a> {
             //    both specified by semver-compatible tags, then those values
             //    will be used. Otherwise any duplicated addons will result in
             //    an error.
-            if let Some((existing, dep_prev)) = to_install.get(&name) {
+            if let Some((existing, dep_prev, chain_prev)) = to_install.get(&name) {
                 match existing.version.as_ref() {
                     None => {
                         // At this point, there's no safe way to verify that the
                         // "existing" and "next" addons are compatible. The only
                         // possibility is if the [`Dependency`] specifications
-                        // are equivalent.
-                        if &dep != dep_prev {
-                            return Err(Error::Incompatible(
-                                name,
-                                None::<Version>,
-                                None::<Version>,
-                            ));
+                        // are equivalent. Requested features are ignored here
+                        // because they've already been merged above.
+                        let dep_cmp = Dependency {
+                            features: dep_prev.features.clone(),
+                            ..dep.clone()
+                        };
+
+                        if &dep_cmp != dep_prev {
+                            return Err(Error::Incompatible(Conflict::incompatible(
+                                &name,
+                                chain_prev.clone(),
+                                chain,
+                            )));
                         }
                     }
                     Some(v_prev) => match addon.version.as_ref() {
                         None => {
-                            return Err(Error::Incompatible(
-                                name,
-                                None::<Version>,
-                                Some(v_prev.clone()),
-                            ))
+                            return Err(Error::Incompatible(Conflict::incompatible(
+                                &name,
+                                chain_prev.clone(),
+                                chain,
+                            )))
                         }
                         Some(v_next) => {
                             if v_next.major != v_prev.major {
-                                return Err(Error::Incompatible(
-                                    name,
-                                    Some(v_prev.clone()),
-                                    Some(v_next.clone()),
-                                ));
+                                return Err(Error::Incompatible(Conflict::incompatible(
+                                    &name,
+                                    chain_prev.clone(),
+                                    chain,
+                                )));
                             }
 
                             // Only update the addon version if "current" is
-                            // newer (after checking compatible major versions).
-                            match v_prev.cmp_precedence(v_next) {
+                            // preferred by the [`Resolution`] strategy (after
+                            // checking compatible major versions).
+                            let ordering = match self.resolution {
+                                Resolution::Highest => v_prev.cmp_precedence(v_next),
+                                Resolution::Lowest => v_next.cmp_precedence(v_prev),
+                            };
+
+                            match ordering {
                                 std::cmp::Ordering::Less => {
                                     // Check if the dependency to be replaced
                                     // requires an exact version.
                                     if dep_prev.is_exact_version_required() {
                                         return Err(Error::ExactVersionRequired(
-                                            name,
-                                            v_prev.to_owned(),
-                                            v_next.to_owned(),
+                                            Conflict::exact_version_required(
+                                                &name,
+                                                chain_prev.clone(),
+                                                chain,
+                                            ),
                                         ));
                                     }
                                 }
@@ -232,9 +418,11 @@ impl<'a> Install<'a> {
                                     // requires an exact version.
                                     if dep.is_exact_version_required() {
                                         return Err(Error::ExactVersionRequired(
-                                            name,
-                                            v_next.to_owned(),
-                                            v_prev.to_owned(),
+                                            Conflict::exact_version_required(
+                                                &name,
+                                                chain,
+                                                chain_prev.clone(),
+                                            ),
                                         ));
                                     }
 
@@ -254,13 +442,203 @@ impl<'a> Install<'a> {
             //    within the Godot project's "addons" folder.
             let subfolder = addon.subfolder.to_owned();
 
-            if subfolders.contains_key(&subfolder) {
-                let other = subfolders.get(&subfolder).unwrap();
-                return Err(Error::Subfolder(subfolder, name, other.to_owned()));
+            if let Some(other) = subfolders.get(&subfolder) {
+                let chain_other = to_install
+                    .get(other)
+                    .map(|(_, _, c)| c.clone())
+                    .unwrap_or_default();
+
+                return Err(Error::Subfolder(
+                    subfolder.clone(),
+                    Conflict::subfolder(&subfolder, chain_other, chain),
+                ));
             }
 
             subfolders.insert(subfolder, name.clone());
 
+            // 3. An addon cannot be installed alongside any addon it declares a
+            //    conflict with. Additionally, an addon which provides another
+            //    addon's name satisfies all dependencies on that name.
+            if let Some(m) = addon.manifest.as_ref() {
+                let project = m.project();
+
+                for other in project.get_conflicts() {
+                    if other == name {
+                        continue;
+                    }
+
+                    let chain_other = to_install.get(&other).or_else(|| {
+                        provided
+                            .get(&other)
+                            .filter(|p| **p != name)
+                            .and_then(|p| to_install.get(p))
+                    });
+
+                    if let Some((_, _, chain_other)) = chain_other {
+                        return Err(Error::Conflicting(Conflict::conflicting(
+                            &other,
+                            chain,
+                            chain_other.clone(),
+                        )));
+                    }
+
+                    conflicts.insert(other, name.clone());
+                }
+
+                for other in project.get_provides() {
+                    if other == name {
+                        continue;
+                    }
+
+                    if let Some(declared_by) = conflicts.get(&other).filter(|n| **n != name) {
+                        return Err(Error::Conflicting(Conflict::conflicting(
+                            &other,
+                            to_install
+                                .get(declared_by)
+                                .map(|(_, _, c)| c.clone())
+                                .unwrap_or_default(),
+                            chain,
+                        )));
+                    }
+
+                    // The project requires an addon with the provided name
+                    // directly, so both are wanted, which is an error.
+                    if direct.contains(&other) {
+                        let chain_direct = Chain::default().join(Link {
+                            name: other.to_owned(),
+                            requirement: None,
+                        });
+
+                        return Err(Error::Conflicting(Conflict::conflicting(
+                            &other,
+                            chain_direct,
+                            chain,
+                        )));
+                    }
+
+                    // An addon with the provided name was already selected, so
+                    // this addon supersedes it. The superseded addon is removed
+                    // along with the parts of the graph that only it required
+                    // (including any features it enabled).
+                    if let Some((_, _, chain_existing)) = to_install.get(&other) {
+                        println!("addon '{}' is provided by: {}", other, name);
+
+                        superseded.insert(
+                            other.clone(),
+                            (name.clone(), chain_existing.clone(), chain.clone()),
+                        );
+
+                        // (requester name, addon name) requests to withdraw; if
+                        // no requester is set, then the addon is removed.
+                        let mut to_withdraw = VecDeque::from([(None, other.clone())]);
+
+                        while let Some((requester, target)) = to_withdraw.pop_front() {
+                            if let Some(requester) = requester {
+                                let Some(by) = requests.get_mut(&target) else {
+                                    continue;
+                                };
+
+                                by.remove(&requester);
+
+                                if !by.is_empty() {
+                                    let enabled_next =
+                                        by.values().flatten().cloned().collect::<BTreeSet<_>>();
+                                    let enabled_prev = features
+                                        .insert(target.clone(), enabled_next.clone())
+                                        .unwrap_or_default();
+
+                                    // Withdraw the optional dependencies which
+                                    // were only enabled by the requester.
+                                    let selected = to_install
+                                        .get(&target)
+                                        .and_then(|(a, d, _)| Some((a.manifest.as_ref()?, d)));
+
+                                    if let Some((m, dep_prev)) =
+                                        selected.filter(|_| enabled_next.len() < enabled_prev.len())
+                                    {
+                                        let path_prev =
+                                            dep_prev.download().map_err(Error::Dependency)?;
+
+                                        let targets = self.targets.iter().copied();
+                                        let prev = m
+                                            .dependencies(
+                                                &path_prev,
+                                                false,
+                                                targets,
+                                                enabled_prev.iter().map(String::as_str),
+                                            )
+                                            .map_err(Error::Config)?;
+
+                                        let targets = self.targets.iter().copied();
+                                        let next = m
+                                            .dependencies(
+                                                &path_prev,
+                                                false,
+                                                targets,
+                                                enabled_next.iter().map(String::as_str),
+                                            )
+                                            .map_err(Error::Config)?;
+
+                                        to_withdraw.extend(
+                                            prev.into_iter()
+                                                .filter(|d| {
+                                                    !next.iter().any(|n| n.addon == d.addon)
+                                                })
+                                                .filter_map(|d| d.addon)
+                                                .map(|n| (Some(target.clone()), n)),
+                                        );
+                                    }
+
+                                    continue;
+                                }
+                            }
+
+                            // Nothing requires the addon anymore, so remove it
+                            // and withdraw its own requests.
+                            to_install.remove(&target);
+                            features.remove(&target);
+                            requests.remove(&target);
+                            subfolders.retain(|_, n| *n != target);
+                            conflicts.retain(|_, n| *n != target);
+                            provided.retain(|_, n| *n != target);
+
+                            to_withdraw.extend(
+                                requests
+                                    .iter()
+                                    .filter(|(_, by)| by.contains_key(&target))
+                                    .map(|(n, _)| (Some(target.clone()), n.clone())),
+                            );
+                        }
+
+                        // Revisit dependencies which were satisfied by a removed
+                        // provider and drop any queued by a removed addon.
+                        let (requeue, kept): (Vec<_>, Vec<_>) =
+                            skipped.drain(..).partition(|(d, _)| {
+                                d.addon.as_ref().is_some_and(|n| !provided.contains_key(n))
+                            });
+
+                        skipped = kept;
+                        to_visit.extend(requeue);
+
+                        let is_selected =
+                            |c: &Chain| c.last().map_or(true, |l| to_install.contains_key(&l.name));
+
+                        to_visit.retain(|(_, c)| is_selected(c));
+                        skipped.retain(|(_, c)| is_selected(c));
+
+                        // This addon was only required through the one it just
+                        // superseded, so it can't be selected either (checked
+                        // once resolution completes).
+                        if !requests.contains_key(&name) {
+                            provided.insert(other, name.clone());
+                            continue 'dep;
+                        }
+                    }
+
+                    provided.insert(other, name.clone());
+                }
+            }
+
             // Now, register the valid addon as to-be-installed and add any
             // dependencies it has to the queue.
 
@@ -270,17 +648,479 @@ impl<'a> Install<'a> {
                 let targets = self.targets.iter().copied();
                 let dev = false; // Don't install transitive dev-only addons.
 
+                let enabled = features.get(&name).into_iter().flatten();
+
                 let deps = m
-                    .dependencies(path_dep, dev, targets)
+                    .dependencies(path_dep, dev, targets, enabled.map(String::as_str))
                     .map_err(Error::Config)?;
 
-                to_visit.extend(deps);
+                to_visit.extend(deps.into_iter().map(|d| (d, chain.clone())));
+            }
+
+            to_install.insert(name, (addon, dep.clone(), chain));
+        }
+
+        // A superseding addon may no longer be selected if it was only
+        // required through the addon it superseded.
+        for (other, (name, chain_existing, chain)) in superseded.iter() {
+            if !to_install.contains_key(name) {
+                return Err(Error::Conflicting(Conflict::conflicting(
+                    other,
+                    chain_existing.clone(),
+                    chain.clone(),
+                )));
+            }
+        }
+
+        self.check_peers(&to_install, &provided)?;
+        Install::check_godot(path, &to_install)?;
+
+        let mut unused = patches
+            .keys()
+            .filter(|n| !patched.contains(*n))
+            .collect::<Vec<_>>();
+        unused.sort();
+
+        for name in unused {
+            println!("warning: patch for addon '{}' was not used", name);
+        }
+
+        Ok(to_install.into_values().map(|(a, _, _)| a).collect())
+    }
+
+    /// `check_peers` validates that the peer addons declared by each resolved
+    /// [`Addon`] are present in the resolved set and satisfy the declared
+    /// version requirements. Missing peers are only reported as warnings if
+    /// `allow_missing_peers` is set. A peer may also be satisfied by any addon
+    /// which provides it.
+    fn check_peers(
+        &self,
+        resolved: &HashMap<String, (Addon, Dependency, Chain)>,
+        provided: &HashMap<String, String>,
+    ) -> Result<(), Error> {
+        let mut names = resolved.keys().collect::<Vec<_>>();
+        names.sort();
+
+        for name in names {
+            let (addon, _, chain) = resolved.get(name).unwrap();
+
+            let peers = match addon.manifest.as_ref() {
+                None => continue,
+                Some(m) => m.peers().map_err(Error::Config)?,
+            };
+
+            let mut peers = peers.into_iter().collect::<Vec<_>>();
+            peers.sort_by(|a, b| a.0.cmp(&b.0));
+
+            for (peer, req) in peers {
+                // NOTE: A peer satisfied by a providing addon can't have its
+                // version verified, since the versions are unrelated.
+                if !resolved.contains_key(&peer) && provided.contains_key(&peer) {
+                    continue;
+                }
+
+                let version = match resolved.get(&peer) {
+                    None => {
+                        if !self.allow_missing_peers {
+                            return Err(Error::MissingPeer(chain.clone(), peer, req));
+                        }
+
+                        println!(
+                            "warning: missing peer addon '{}' ({}) required by: {}",
+                            peer, req, chain
+                        );
+
+                        continue;
+                    }
+                    Some((a, _, _)) => a.version.as_ref(),
+                };
+
+                match version {
+                    None => println!(
+                        "warning: cannot verify version of peer addon '{}' ({}) required by: {}",
+                        peer, req, chain
+                    ),
+                    Some(v) => {
+                        if !req.matches(v) {
+                            return Err(Error::IncompatiblePeer(
+                                chain.clone(),
+                                peer,
+                                req,
+                                v.to_owned(),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `check_godot` validates that each resolved [`Addon`] is compatible with
+    /// the _Godot_ engine version used by the project at `path`. Both the
+    /// `godot` requirement of an addon's [`Manifest`] and the
+    /// `compatibility_minimum` of any of its `.gdextension` files are checked
+    /// against each of the project's engine versions (see
+    /// [`Install::godot_versions`]).
+    fn check_godot(
+        path: &Path,
+        resolved: &HashMap<String, (Addon, Dependency, Chain)>,
+    ) -> Result<(), Error> {
+        let versions = Install::godot_versions(path)?;
+
+        let mut names = resolved.keys().collect::<Vec<_>>();
+        names.sort();
+
+        for name in names {
+            let (addon, _, chain) = resolved.get(name).unwrap();
+
+            let mut reqs = Vec::<VersionReq>::new();
+
+            if let Some(m) = addon.manifest.as_ref() {
+                if let Some(req) = m.godot().map_err(Error::Config)? {
+                    if versions.is_empty() {
+                        println!(
+                            "warning: cannot verify Godot version ({}) required by: {}",
+                            req, chain
+                        );
+                    }
+
+                    reqs.push(req);
+                }
+            }
+
+            for ext in FileQuery::<Extension>::builder()
+                .path(&addon.path)
+                .build()
+                .into_iter()
+                .filter_map(|(_, e)| e.ok())
+            {
+                if let Some(v) = ext.compatibility_minimum() {
+                    let req = VersionReq::parse(&format!(">={}", v))
+                        .expect("failed to create version requirement");
+
+                    reqs.push(req);
+                }
+            }
+
+            for req in reqs {
+                for (source, v, is_partial) in &versions {
+                    if !Install::matches_godot(&req, v, *is_partial) {
+                        return Err(Error::IncompatibleGodot(
+                            chain.clone(),
+                            req,
+                            v.to_owned(),
+                            source.to_owned(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `godot_versions` returns the _Godot_ engine versions declared by the
+    /// project at `path`, along with the name of the file which declared each
+    /// one. Both the optional `.godot-version` file and the `config/features`
+    /// property of `project.godot` are read. The latter only records the major
+    /// and minor version, so it's flagged as partial (as is a `.godot-version`
+    /// without a patch version).
+    fn godot_versions(path: &Path) -> Result<Vec<(String, Version, bool)>, Error> {
+        let mut versions = vec![];
+
+        match std::fs::read_to_string(path.join(GODOT_VERSION_FILENAME)) {
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => (),
+            Err(e) => return Err(Error::Io(e)),
+            Ok(contents) => {
+                let v = godot::parse_version(&contents).ok_or(Error::Io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("invalid Godot version: {}", contents.trim()),
+                )))?;
+
+                let is_partial = contents
+                    .trim()
+                    .split(['.', '-', '+'])
+                    .map_while(|s| s.parse::<u64>().ok())
+                    .count()
+                    < 3;
+
+                versions.push((GODOT_VERSION_FILENAME.to_owned(), v, is_partial));
+            }
+        }
+
+        let file_name = ProjectSettings::file_name().unwrap();
+
+        match ProjectSettings::parse_file(path.join(file_name)) {
+            Err(ParsableError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => (),
+            Err(e) => return Err(Error::Load(anyhow::anyhow!(e))),
+            Ok(settings) => {
+                if let Some(v) = settings.version() {
+                    versions.push((file_name.to_owned(), v, true));
+                }
             }
+        }
+
+        Ok(versions)
+    }
+
+    /// `matches_godot` returns whether the _Godot_ engine `version` satisfies
+    /// `req`. A partial version (i.e. one without a patch version) matches if
+    /// any of its patch releases would. Because a requirement only changes at
+    /// the patch versions it names, only those (and their neighbors) are tried.
+    fn matches_godot(req: &VersionReq, version: &Version, is_partial: bool) -> bool {
+        if !is_partial {
+            return req.matches(version);
+        }
+
+        std::iter::once(0)
+            .chain(
+                req.comparators
+                    .iter()
+                    .filter(|c| c.major == version.major && c.minor == Some(version.minor))
+                    .filter_map(|c| c.patch)
+                    .flat_map(|p| [p.saturating_sub(1), p, p.saturating_add(1)]),
+            )
+            .any(|patch| req.matches(&Version::new(version.major, version.minor, patch)))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                Struct: Chain                               */
+/* -------------------------------------------------------------------------- */
+
+/// `Chain` records the sequence of requirements which led from the root
+/// project manifest to a specific [`Addon`]. It's used to explain dependency
+/// resolution failures to the user.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Chain(Vec<Link>);
+
+/* ------------------------------- Impl: Chain ------------------------------ */
+
+impl Chain {
+    /* --------------------------- Methods: Public -------------------------- */
+
+    /// `join` returns a new [`Chain`] with `link` appended to the end of it.
+    pub fn join(&self, link: Link) -> Chain {
+        let mut links = self.0.clone();
+        links.push(link);
+
+        Chain(links)
+    }
+
+    /// `is_direct` returns whether the [`Chain`] ends in a dependency declared
+    /// directly by the root project manifest.
+    pub fn is_direct(&self) -> bool {
+        self.0.len() == 1
+    }
+
+    /// `last` returns the final [`Link`] in the [`Chain`], if any.
+    pub fn last(&self) -> Option<&Link> {
+        self.0.last()
+    }
+
+    /// `manifest` describes the manifest which declared the final [`Link`] in
+    /// the [`Chain`]; this is either the root project manifest or the manifest
+    /// of the preceding addon.
+    pub fn manifest(&self) -> String {
+        match self.0.len() {
+            0 | 1 => String::from("the project manifest"),
+            n => format!("the manifest of '{}'", self.0[n - 2].name),
+        }
+    }
+}
+
+/* ------------------------------ Impl: Display ----------------------------- */
+
+impl std::fmt::Display for Chain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("project")?;
+
+        for link in &self.0 {
+            write!(f, " → {}", link)?;
+        }
+
+        Ok(())
+    }
+}
+
+/* ------------------------------ Struct: Link ------------------------------ */
+
+/// `Link` is a single requirement within a [`Chain`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Link {
+    /// The name of the required addon.
+    pub name: String,
+    /// The version requirement declared by the manifest which included the
+    /// addon, if it specified one.
+    pub requirement: Option<String>,
+}
+
+/* ------------------------------- Impl: Link ------------------------------- */
+
+impl Link {
+    /// `new` creates a [`Link`] describing the provided [`Dependency`], using
+    /// the release or git reference it specifies as the requirement. Path-based
+    /// dependencies have no requirement.
+    pub fn new(name: &str, dep: &Dependency) -> Link {
+        let requirement = match &dep.source {
+            Source::Git(g) => g.reference.as_ref().map(Reference::to_string),
+            Source::Release(r) => Some(r.tag.to_owned()),
+            Source::Path { .. } => None,
+        };
+
+        Link {
+            name: name.to_owned(),
+            requirement,
+        }
+    }
+}
+
+/* ------------------------------ Impl: Display ----------------------------- */
+
+impl std::fmt::Display for Link {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.requirement.as_ref() {
+            None => f.write_str(&self.name),
+            Some(v) => write!(f, "{} {}", self.name, v),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                              Struct: Conflict                              */
+/* -------------------------------------------------------------------------- */
+
+/// `Conflict` describes two competing requirements found during dependency
+/// resolution, along with a suggestion for how the user might fix it.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Conflict {
+    /// The name of the conflicting addon (or subfolder).
+    pub name: String,
+    /// The [`Chain`] which was selected first during resolution.
+    pub selected: Chain,
+    /// The [`Chain`] which conflicts with the `selected` one.
+    pub other: Chain,
+    /// A suggested resolution to the conflict.
+    pub help: String,
+}
+
+/* ----------------------------- Impl: Conflict ----------------------------- */
+
+impl Conflict {
+    /* --------------------------- Methods: Public -------------------------- */
+
+    /// `incompatible` creates a [`Conflict`] for two requirements of the same
+    /// addon which cannot be reconciled.
+    pub fn incompatible(name: &str, selected: Chain, other: Chain) -> Conflict {
+        let help = match Conflict::find_direct(&selected, &other) {
+            Some(link) => format!(
+                "update '{}' in the project manifest to a version compatible with both \
+                requirements, or use 'gdpack replace' to substitute one of them",
+                link.name,
+            ),
+            None => format!(
+                "add '{}' to the project manifest with a version compatible with both \
+                requirements, or use 'gdpack replace' to substitute one of them",
+                name,
+            ),
+        };
+
+        Conflict {
+            name: name.to_owned(),
+            selected,
+            other,
+            help,
+        }
+    }
+
+    /// `exact_version_required` creates a [`Conflict`] for an addon whose exact
+    /// version is required by `selected`, but a different version is required
+    /// by `other`.
+    pub fn exact_version_required(name: &str, selected: Chain, other: Chain) -> Conflict {
+        let help = match other.last().and_then(|l| l.requirement.as_ref()) {
+            Some(r) => format!(
+                "change '{}' in {} to a version matching '{}' or remove its install \
+                hooks so that another version may be selected",
+                name,
+                selected.manifest(),
+                r,
+            ),
+            None => format!(
+                "remove the install hooks from '{}' in {} so that another version may \
+                be selected",
+                name,
+                selected.manifest(),
+            ),
+        };
+
+        Conflict {
+            name: name.to_owned(),
+            selected,
+            other,
+            help,
+        }
+    }
+
+    /// `conflicting` creates a [`Conflict`] for the addon `name`, which can't be
+    /// installed alongside the addon at the end of `selected` because one of
+    /// them declares a conflict with the other.
+    pub fn conflicting(name: &str, selected: Chain, other: Chain) -> Conflict {
+        let help = format!(
+            "remove one of the conflicting addons from the dependency graph, or use \
+            'gdpack replace' to substitute '{}' with a compatible addon",
+            name,
+        );
+
+        Conflict {
+            name: name.to_owned(),
+            selected,
+            other,
+            help,
+        }
+    }
 
-            to_install.insert(name, (addon, dep.clone()));
+    /// `subfolder` creates a [`Conflict`] for two different addons which would
+    /// be installed into the same `subfolder`.
+    pub fn subfolder(subfolder: &str, selected: Chain, other: Chain) -> Conflict {
+        let help = format!(
+            "use 'gdpack replace' to substitute '{}' with '{}' (or vice versa) if they \
+            provide the same functionality, otherwise remove one of them",
+            selected
+                .last()
+                .map(|l| l.name.as_str())
+                .unwrap_or(subfolder),
+            other.last().map(|l| l.name.as_str()).unwrap_or(subfolder),
+        );
+
+        Conflict {
+            name: subfolder.to_owned(),
+            selected,
+            other,
+            help,
         }
+    }
+
+    /* -------------------------- Methods: Private -------------------------- */
+
+    /// `find_direct` returns the first direct dependency [`Link`] of either
+    /// [`Chain`] that the user can modify within the project manifest.
+    fn find_direct<'b>(selected: &'b Chain, other: &'b Chain) -> Option<&'b Link> {
+        [selected, other]
+            .into_iter()
+            .find(|c| c.is_direct())
+            .and_then(Chain::last)
+    }
+}
 
-        Ok(to_install.into_values().map(|(a, _)| a).collect())
+/* ------------------------------ Impl: Display ----------------------------- */
+
+impl std::fmt::Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "  {}", self.selected)?;
+        writeln!(f, "  {}", self.other)?;
+        write!(f, "help: {}", self.help)
     }
 }
 
@@ -292,26 +1132,32 @@ impl<'a> Install<'a> {
 pub enum Error {
     #[error(transparent)]
     Config(crate::config::manifest::Error),
+    #[error("conflicting addons found for '{}':\n{0}", .0.name)]
+    Conflicting(Conflict),
     #[error(transparent)]
     Dependency(super::dependency::Error),
-    #[error("exact version of '{0}' required ('{1:?}'), but selected: {2:?}")]
-    ExactVersionRequired(String, Version, Version),
+    #[error("exact version of '{}' required, but another was requested:\n{0}", .0.name)]
+    ExactVersionRequired(Conflict),
     #[error(transparent)]
     Io(std::io::Error),
-    // TODO: Provide additional information about the dependency chain that
-    // resulted in each addon being included.
-    #[error("incompatible versions found for addon '{0}': {1:?}, {2:?}")]
-    Incompatible(String, Option<Version>, Option<Version>),
+    #[error("incompatible versions found for addon '{}':\n{0}", .0.name)]
+    Incompatible(Conflict),
+    #[error("incompatible Godot version (requires {1}, found {2} in '{3}') for: {0}")]
+    IncompatibleGodot(Chain, VersionReq, Version, String),
+    #[error("incompatible peer addon '{1}' found (requires {2}, found {3}) for: {0}")]
+    IncompatiblePeer(Chain, String, VersionReq, Version),
     #[error("failed to install addon {0}: {1:?}")]
     Install(String, std::io::Error),
     #[error(transparent)]
     Load(anyhow::Error),
     #[error("could not determine addon name")]
     MissingName,
+    #[error("missing peer addon '{1}' (requires {2}) for: {0}")]
+    MissingPeer(Chain, String, VersionReq),
     #[error(transparent)]
     Project(super::project::Error),
-    #[error("duplicate subfolder found between addons '{1}' and '{2}': {0}")]
-    Subfolder(String, String, String),
+    #[error("duplicate subfolder found between addons: {0}\n{1}")]
+    Subfolder(String, Conflict),
 }
 
 /* -------------------------------------------------------------------------- */
@@ -332,13 +1178,18 @@ mod tests {
     use crate::config::plugin::Plugin;
     use crate::config::Configuration;
     use crate::config::Manifest;
+    use crate::config::Parsable;
     use crate::config::Persistable;
     use crate::core::Addon;
     use crate::core::Dependency;
     use crate::core::Source;
 
+    use super::Chain;
+    use super::Conflict;
     use super::Error;
     use super::Install;
+    use super::Link;
+    use super::Resolution;
 
     /* ------------------------------ Test: run ----------------------------- */
 
@@ -502,6 +1353,61 @@ mod tests {
         );
     }
 
+    #[rstest]
+    #[case(vec![], vec!["1"])]
+    #[case(vec!["l10n"], vec!["1", "2"])]
+    fn test_installer_run_enables_project_features(
+        #[case] features: Vec<&str>,
+        #[case] want: Vec<&str>,
+    ) {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: Two simple dependencies that exist on disk.
+        let deps = ["1", "2"]
+            .into_iter()
+            .map(|n| {
+                let dep = TestDep::builder()
+                    .name(n)
+                    .addon(DepType::Plugin(Version::new(1, 0, 0)))
+                    .build()
+                    .init(tmp.path(), n)
+                    .unwrap();
+
+                (n, dep)
+            })
+            .collect::<Vec<_>>();
+
+        // Given: A root manifest with a feature enabling an optional dependency.
+        let m = Manifest::parse(
+            r#"
+[addons]
+1 = { path = "1" }
+2 = { path = "2", optional = true }
+
+[features]
+l10n = ["2"]
+"#,
+        )
+        .unwrap();
+
+        // When: An installation is run with the specified project features.
+        let got = Install::builder()
+            .features(features)
+            .manifest(&m)
+            .build()
+            .resolve_addons(tmp.path());
+
+        // Then: Only the optional dependencies enabled by a feature are included.
+        assert_addons_eq!(
+            got.unwrap(),
+            deps.iter()
+                .filter(|(n, _)| want.contains(n))
+                .map(|(_, d)| Addon::try_from(d).unwrap())
+                .collect::<Vec<_>>()
+        );
+    }
+
     #[rstest]
     fn test_installer_run_direct_dep_with_lower_version_fails() {
         // Given: A temporary test directory for creating dependencies.
@@ -549,25 +1455,26 @@ mod tests {
             .build()
             .resolve_addons(path_project);
 
-        // Then: An error is returned.
-        assert!(got.is_err_and(|e| e.to_string()
-            == Error::ExactVersionRequired(
-                "1".to_owned(),
-                Version::new(1, 0, 0),
-                Version::new(1, 2, 3)
-            )
-            .to_string()));
+        // Then: An error is returned which describes both dependency chains.
+        match got {
+            Err(Error::ExactVersionRequired(conflict)) => {
+                assert_eq!(conflict.name, "1");
+                assert_eq!(conflict.selected.to_string(), "project → 1");
+                assert_eq!(conflict.other.to_string(), "project → 2 → 1");
+            }
+            _ => panic!("expected 'ExactVersionRequired' error"),
+        }
     }
 
     #[rstest]
-    fn test_installer_run_direct_dep_with_higher_version_succeeds() {
+    fn test_installer_run_transitive_dep_with_incompatible_version_fails() {
         // Given: A temporary test directory for creating dependencies.
         let tmp = tempfile::tempdir().unwrap();
 
         // Given: A directory containing the test project.
         let path_project = tmp.path();
 
-        // Given: An indirect dependency, "1".
+        // Given: An indirect dependency, "1", with an older major version.
         let dep1_indirect = TestDep::builder()
             .name("1")
             .addon(DepType::Plugin(Version::new(1, 0, 0)))
@@ -579,16 +1486,16 @@ mod tests {
         // Given: A new direct dependency that utilizes `dep1_indirect`.
         let dep2 = TestDep::builder()
             .name("2")
-            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .addon(DepType::Plugin(Version::new(2, 0, 0)))
             .deps(vec![dep1_indirect.clone()])
             .build()
             .init(path_project, "./2")
             .unwrap();
 
-        // Given: A direct dependency, "1", with a lower version.
+        // Given: A direct dependency, "1", with a newer major version.
         let dep1_direct = TestDep::builder()
             .name("1")
-            .addon(DepType::Plugin(Version::new(1, 2, 3)))
+            .addon(DepType::Plugin(Version::new(2, 0, 0)))
             .build()
             .init(path_project, "./2/1/direct")
             .unwrap()
@@ -605,18 +1512,21 @@ mod tests {
             .build()
             .resolve_addons(path_project);
 
-        // Then: The resolved addons match expectations.
-        assert_addons_eq!(
-            got.unwrap(),
+        // Then: An error is returned which describes both dependency chains.
+        let err = got.expect_err("expected an error");
+        assert!(matches!(err, Error::Incompatible(_)));
+        assert_eq!(
+            err.to_string().lines().take(3).collect::<Vec<_>>(),
             vec![
-                Addon::try_from(&dep1_direct).unwrap(),
-                Addon::try_from(&dep2).unwrap(),
+                "incompatible versions found for addon '1':",
+                "  project → 1",
+                "  project → 2 → 1",
             ]
         );
     }
 
     #[rstest]
-    fn test_installer_run_direct_dep_with_same_version_succeeds() {
+    fn test_installer_run_direct_dep_with_higher_version_succeeds() {
         // Given: A temporary test directory for creating dependencies.
         let tmp = tempfile::tempdir().unwrap();
 
@@ -641,15 +1551,14 @@ mod tests {
             .init(path_project, "./2")
             .unwrap();
 
-        // Given: A direct dependency, "1", with the same version.
-        let mut dep1_direct = TestDep::builder()
+        // Given: A direct dependency, "1", with a lower version.
+        let dep1_direct = TestDep::builder()
             .name("1")
-            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .addon(DepType::Plugin(Version::new(1, 2, 3)))
             .build()
             .init(path_project, "./2/1/direct")
             .unwrap()
             .rooted_at(tmp.path());
-        dep1_direct.hooks.post = Some("echo test".to_owned());
 
         // Given: A root manifest with direct dependencies.
         let mut m = Manifest::default();
@@ -672,18 +1581,833 @@ mod tests {
         );
     }
 
-    /* ---------------------------------------------------------------------- */
-    /*                             Struct: TestDep                            */
-    /* ---------------------------------------------------------------------- */
+    #[rstest]
+    fn test_installer_run_lowest_resolution_selects_lower_version() {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
 
-    #[derive(Clone, Debug, TypedBuilder)]
-    struct TestDep {
-        #[builder(default = DepType::Assets)]
-        addon: DepType,
+        // Given: A directory containing the test project.
+        let path_project = tmp.path();
+
+        // Given: An indirect dependency, "1".
+        let dep1_indirect = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .build()
+            .init(path_project, "./2/1/indirect")
+            .unwrap()
+            .rooted_at(tmp.path());
+
+        // Given: A new direct dependency that utilizes `dep1_indirect`.
+        let dep2 = TestDep::builder()
+            .name("2")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .deps(vec![dep1_indirect.clone()])
+            .build()
+            .init(path_project, "./2")
+            .unwrap();
+
+        // Given: A direct dependency, "1", with a higher version.
+        let dep1_direct = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 2, 3)))
+            .build()
+            .init(path_project, "./2/1/direct")
+            .unwrap()
+            .rooted_at(tmp.path());
+
+        // Given: A root manifest with direct dependencies.
+        let mut m = Manifest::default();
+        m.addons_mut(&Query::prod()).insert(&dep1_direct);
+        m.addons_mut(&Query::prod()).insert(&dep2);
+
+        // When: An installation is run which prefers the lowest versions.
+        let got = Install::builder()
+            .manifest(&m)
+            .resolution(Resolution::Lowest)
+            .build()
+            .resolve_addons(path_project);
+
+        // Then: The lower version of "1" is selected.
+        assert_addons_eq!(
+            got.unwrap(),
+            vec![
+                Addon::try_from(&dep1_indirect).unwrap(),
+                Addon::try_from(&dep2).unwrap(),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_installer_run_direct_dep_with_same_version_succeeds() {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A directory containing the test project.
+        let path_project = tmp.path();
+
+        // Given: An indirect dependency, "1".
+        let dep1_indirect = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .build()
+            .init(path_project, "./2/1/indirect")
+            .unwrap()
+            .rooted_at(tmp.path());
+
+        // Given: A new direct dependency that utilizes `dep1_indirect`.
+        let dep2 = TestDep::builder()
+            .name("2")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .deps(vec![dep1_indirect.clone()])
+            .build()
+            .init(path_project, "./2")
+            .unwrap();
+
+        // Given: A direct dependency, "1", with the same version.
+        let mut dep1_direct = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .build()
+            .init(path_project, "./2/1/direct")
+            .unwrap()
+            .rooted_at(tmp.path());
+        dep1_direct.hooks.post = Some("echo test".to_owned());
+
+        // Given: A root manifest with direct dependencies.
+        let mut m = Manifest::default();
+        m.addons_mut(&Query::prod()).insert(&dep1_direct);
+        m.addons_mut(&Query::prod()).insert(&dep2);
+
+        // When: An installation is run for the default target/environment.
+        let got = Install::builder()
+            .manifest(&m)
+            .build()
+            .resolve_addons(path_project);
+
+        // Then: The resolved addons match expectations.
+        assert_addons_eq!(
+            got.unwrap(),
+            vec![
+                Addon::try_from(&dep1_direct).unwrap(),
+                Addon::try_from(&dep2).unwrap(),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_installer_run_patched_transitive_dep_succeeds() {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A directory containing the test project.
+        let path_project = tmp.path();
+
+        // Given: An indirect dependency, "1".
+        let dep1_indirect = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .build()
+            .init(path_project, "./2/1")
+            .unwrap()
+            .rooted_at(tmp.path());
+
+        // Given: A direct dependency that utilizes `dep1_indirect`.
+        let dep2 = TestDep::builder()
+            .name("2")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .deps(vec![dep1_indirect.clone()])
+            .build()
+            .init(path_project, "./2")
+            .unwrap();
+
+        // Given: A patched version of "1" located elsewhere.
+        let dep1_patch = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 1)))
+            .build()
+            .init(path_project, "./patch/1")
+            .unwrap();
+
+        // Given: A root manifest which patches "1" and depends on "2".
+        let mut m = Manifest::parse(&format!(
+            "[patch]\n1 = {{ path = {:?} }}",
+            path_project.join("patch/1").to_str().unwrap(),
+        ))
+        .unwrap();
+        m.addons_mut(&Query::prod()).insert(&dep2);
+
+        // When: An installation is run for the default target/environment.
+        let got = Install::builder()
+            .manifest(&m)
+            .build()
+            .resolve_addons(path_project);
+
+        // Then: The patched addon is selected in place of the original.
+        assert_addons_eq!(
+            got.unwrap(),
+            vec![
+                Addon::try_from(&dep1_patch).unwrap(),
+                Addon::try_from(&dep2).unwrap(),
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_installer_run_patched_dep_outside_project_succeeds() {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A directory containing the test project.
+        let path_project = tmp.path().join("project");
+
+        // Given: An indirect dependency, "1".
+        let dep1_indirect = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .build()
+            .init(&path_project, "./2/1")
+            .unwrap()
+            .rooted_at(&path_project);
+
+        // Given: A direct dependency that utilizes `dep1_indirect`.
+        let dep2 = TestDep::builder()
+            .name("2")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .deps(vec![dep1_indirect.clone()])
+            .build()
+            .init(&path_project, "./2")
+            .unwrap();
+
+        // Given: A patched version of "1" located outside of the project.
+        let dep1_patch = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 1)))
+            .build()
+            .init(tmp.path(), "./patch/1")
+            .unwrap();
+
+        // Given: A root manifest which patches "1" and depends on "2".
+        let mut m = Manifest::parse("[patch]\n1 = { path = \"../patch/1\" }").unwrap();
+        m.addons_mut(&Query::prod()).insert(&dep2);
+
+        // When: An installation is run for the default target/environment.
+        let got = Install::builder()
+            .manifest(&m)
+            .build()
+            .resolve_addons(&path_project);
+
+        // Then: The patched addon is selected without being a direct dependency.
+        let want = Addon::try_from(&dep1_patch).unwrap();
+        let got = got.unwrap();
+
+        assert_eq!(got.len(), 2);
+        assert!(got
+            .iter()
+            .any(|a| a.subfolder == want.subfolder && a.version == want.version));
+    }
+
+    #[rstest]
+    fn test_installer_run_patched_dep_keeps_hooks() {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A directory containing the test project.
+        let path_project = tmp.path();
+
+        // Given: A direct dependency, "1", with a post-install hook.
+        let mut dep1 = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .build()
+            .init(path_project, "./1")
+            .unwrap();
+
+        dep1.hooks.post = Some("echo installed".to_owned());
+
+        // Given: A patched version of "1" located elsewhere.
+        let dep1_patch = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 1)))
+            .build()
+            .init(path_project, "./patch/1")
+            .unwrap();
+
+        // Given: A root manifest which patches "1" and depends on it.
+        let mut m = Manifest::parse(&format!(
+            "[patch]\n1 = {{ path = {:?} }}",
+            path_project.join("patch/1").to_str().unwrap(),
+        ))
+        .unwrap();
+        m.addons_mut(&Query::prod()).insert(&dep1);
+
+        // When: An installation is run for the default target/environment.
+        let got = Install::builder()
+            .manifest(&m)
+            .build()
+            .resolve_addons(path_project);
+
+        // Then: The patched addon is selected, but keeps the original's hook.
+        let mut want = Addon::try_from(&dep1_patch).unwrap();
+        want.hooks.post = Some("echo installed".to_owned());
+
+        assert_addons_eq!(got.unwrap(), vec![want]);
+    }
+
+    #[rstest]
+    #[case(false, false)]
+    #[case(true, true)]
+    fn test_installer_run_missing_peer(#[case] allow_missing_peers: bool, #[case] ok: bool) {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A direct dependency which requires a peer addon.
+        let dep = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .peers(vec![("2", "^1")])
+            .build()
+            .init(tmp.path(), "./1")
+            .unwrap();
+
+        // Given: A root manifest which does not include the peer addon.
+        let mut m = Manifest::default();
+        m.addons_mut(&Query::prod()).insert(&dep);
+
+        // When: An installation is run for the default target/environment.
+        let got = Install::builder()
+            .allow_missing_peers(allow_missing_peers)
+            .manifest(&m)
+            .build()
+            .resolve_addons(tmp.path());
+
+        // Then: The missing peer is only an error if not explicitly allowed.
+        match ok {
+            true => assert_addons_eq!(got.unwrap(), vec![Addon::try_from(&dep).unwrap()]),
+            false => assert!(matches!(got, Err(Error::MissingPeer(_, p, _)) if p == "2")),
+        }
+    }
+
+    #[rstest]
+    #[case(Version::new(1, 2, 0), true)]
+    #[case(Version::new(2, 0, 0), false)]
+    fn test_installer_run_peer_version(#[case] version: Version, #[case] ok: bool) {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A direct dependency which requires a peer addon.
+        let dep1 = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .peers(vec![("2", "^1.1")])
+            .build()
+            .init(tmp.path(), "./1")
+            .unwrap();
+
+        // Given: The peer addon, provided by the project.
+        let dep2 = TestDep::builder()
+            .name("2")
+            .addon(DepType::Plugin(version))
+            .build()
+            .init(tmp.path(), "./2")
+            .unwrap();
+
+        // Given: A root manifest with direct dependencies.
+        let mut m = Manifest::default();
+        m.addons_mut(&Query::prod()).insert(&dep1);
+        m.addons_mut(&Query::prod()).insert(&dep2);
+
+        // When: An installation is run for the default target/environment.
+        let got = Install::builder()
+            .manifest(&m)
+            .build()
+            .resolve_addons(tmp.path());
+
+        // Then: The peer's version must satisfy the requirement.
+        match ok {
+            true => assert_addons_eq!(
+                got.unwrap(),
+                vec![
+                    Addon::try_from(&dep1).unwrap(),
+                    Addon::try_from(&dep2).unwrap()
+                ]
+            ),
+            false => assert!(matches!(got, Err(Error::IncompatiblePeer(_, p, _, _)) if p == "2")),
+        }
+    }
+
+    #[rstest]
+    fn test_installer_run_conflicting_addons_fails() {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A direct dependency which conflicts with another addon.
+        let dep1 = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .conflicts(vec!["2"])
+            .build()
+            .init(tmp.path(), "./1")
+            .unwrap();
+
+        // Given: The conflicting addon.
+        let dep2 = TestDep::builder()
+            .name("2")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .build()
+            .init(tmp.path(), "./2")
+            .unwrap();
+
+        // Given: A root manifest with both addons as direct dependencies.
+        let mut m = Manifest::default();
+        m.addons_mut(&Query::prod()).insert(&dep1);
+        m.addons_mut(&Query::prod()).insert(&dep2);
+
+        // When: An installation is run for the default target/environment.
+        let got = Install::builder()
+            .manifest(&m)
+            .build()
+            .resolve_addons(tmp.path());
+
+        // Then: An error is returned.
+        assert!(matches!(got, Err(Error::Conflicting(c)) if c.name == "2"));
+    }
+
+    #[rstest]
+    fn test_installer_run_provided_addon_satisfies_dependency() {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: An indirect dependency on a virtual addon name, "virtual".
+        let dep_virtual = TestDep::builder()
+            .name("virtual")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .build()
+            .init(tmp.path(), "./2/virtual")
+            .unwrap();
+
+        // Given: A direct dependency which depends on "virtual".
+        let dep2 = TestDep::builder()
+            .name("2")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .deps(vec![dep_virtual])
+            .build()
+            .init(tmp.path(), "./2")
+            .unwrap();
+
+        // Given: A direct dependency which provides "virtual".
+        let dep1 = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .provides(vec!["virtual"])
+            .build()
+            .init(tmp.path(), "./1")
+            .unwrap();
+
+        // Given: A root manifest with direct dependencies.
+        let mut m = Manifest::default();
+        m.addons_mut(&Query::prod()).insert(&dep1);
+        m.addons_mut(&Query::prod()).insert(&dep2);
+
+        // When: An installation is run for the default target/environment.
+        let got = Install::builder()
+            .manifest(&m)
+            .build()
+            .resolve_addons(tmp.path());
+
+        // Then: The provided addon is not installed.
+        assert_addons_eq!(
+            got.unwrap(),
+            vec![
+                Addon::try_from(&dep1).unwrap(),
+                Addon::try_from(&dep2).unwrap()
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case("1", "2")]
+    #[case("2", "1")]
+    fn test_installer_run_provided_direct_dependency_fails(
+        #[case] provider: &str,
+        #[case] provided: &'static str,
+    ) {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A direct dependency on an addon.
+        let dep_provided = TestDep::builder()
+            .name(provided)
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .build()
+            .init(tmp.path(), format!("./{}", provided))
+            .unwrap();
+
+        // Given: A direct dependency which provides that addon, ordered either
+        // before or after it.
+        let dep_provider = TestDep::builder()
+            .name(provider)
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .provides(vec![provided])
+            .build()
+            .init(tmp.path(), format!("./{}", provider))
+            .unwrap();
+
+        // Given: A root manifest with direct dependencies.
+        let mut m = Manifest::default();
+        m.addons_mut(&Query::prod()).insert(&dep_provided);
+        m.addons_mut(&Query::prod()).insert(&dep_provider);
+
+        // When: An installation is run for the default target/environment.
+        let got = Install::builder()
+            .manifest(&m)
+            .build()
+            .resolve_addons(tmp.path());
+
+        // Then: An error is returned regardless of the order.
+        assert!(matches!(got, Err(Error::Conflicting(c)) if c.name == provided));
+    }
+
+    #[rstest]
+    fn test_installer_run_provided_addon_prunes_superseded_dependencies() {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A virtual addon, "virtual", with a dependency of its own.
+        let dep_extra = TestDep::builder()
+            .name("extra")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .build()
+            .init(tmp.path(), "./1/virtual/extra")
+            .unwrap();
+
+        let dep_virtual = TestDep::builder()
+            .name("virtual")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .deps(vec![dep_extra])
+            .build()
+            .init(tmp.path(), "./1/virtual")
+            .unwrap();
+
+        // Given: A direct dependency which depends on "virtual".
+        let dep1 = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .deps(vec![dep_virtual])
+            .build()
+            .init(tmp.path(), "./1")
+            .unwrap();
+
+        // Given: An indirect dependency which provides "virtual", but which is
+        // only reached after "virtual" was selected.
+        let dep_provider = TestDep::builder()
+            .name("3")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .provides(vec!["virtual"])
+            .build()
+            .init(tmp.path(), "./2/3")
+            .unwrap();
+
+        let dep2 = TestDep::builder()
+            .name("2")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .deps(vec![dep_provider.clone()])
+            .build()
+            .init(tmp.path(), "./2")
+            .unwrap();
+
+        // Given: A root manifest with direct dependencies.
+        let mut m = Manifest::default();
+        m.addons_mut(&Query::prod()).insert(&dep1);
+        m.addons_mut(&Query::prod()).insert(&dep2);
+
+        // When: An installation is run for the default target/environment.
+        let got = Install::builder()
+            .manifest(&m)
+            .build()
+            .resolve_addons(tmp.path());
+
+        // Then: Neither the superseded addon nor its dependency is installed.
+        assert_addons_eq!(
+            got.unwrap(),
+            vec![
+                Addon::try_from(&dep1).unwrap(),
+                Addon::try_from(&dep2).unwrap(),
+                Addon::try_from(&dep_provider).unwrap()
+            ]
+        );
+    }
+
+    #[rstest]
+    fn test_installer_run_provided_addon_keeps_shared_dependencies() {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A dependency which is shared by multiple addons (each with its
+        // own copy of the same version).
+        let dep_shared = TestDep::builder()
+            .name("shared")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .build();
+
+        // Given: A virtual addon, "virtual", with a shared and an exclusive
+        // dependency.
+        let dep_extra = TestDep::builder()
+            .name("extra")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .build()
+            .init(tmp.path(), "./1/virtual/extra")
+            .unwrap();
+
+        let dep_virtual = TestDep::builder()
+            .name("virtual")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .deps(vec![
+                dep_extra,
+                dep_shared.init(tmp.path(), "./1/virtual/shared").unwrap(),
+            ])
+            .build()
+            .init(tmp.path(), "./1/virtual")
+            .unwrap();
+
+        // Given: A direct dependency which depends on "virtual".
+        let dep1 = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .deps(vec![dep_virtual])
+            .build()
+            .init(tmp.path(), "./1")
+            .unwrap();
+
+        // Given: A direct dependency which depends on the shared addon and an
+        // addon providing "virtual".
+        let dep_provider = TestDep::builder()
+            .name("3")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .provides(vec!["virtual"])
+            .build()
+            .init(tmp.path(), "./2/3")
+            .unwrap();
+
+        let dep_shared = dep_shared.init(tmp.path(), "./2/shared").unwrap();
+
+        let dep2 = TestDep::builder()
+            .name("2")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .deps(vec![dep_shared.clone(), dep_provider.clone()])
+            .build()
+            .init(tmp.path(), "./2")
+            .unwrap();
+
+        // Given: A root manifest with direct dependencies.
+        let mut m = Manifest::default();
+        m.addons_mut(&Query::prod()).insert(&dep1);
+        m.addons_mut(&Query::prod()).insert(&dep2);
+
+        // When: An installation is run for the default target/environment.
+        let got = Install::builder()
+            .manifest(&m)
+            .build()
+            .resolve_addons(tmp.path());
+
+        // Then: The shared dependency is kept, but the superseded addon's
+        // exclusive dependency is not.
+        assert_addons_eq!(
+            got.unwrap(),
+            vec![
+                Addon::try_from(&dep1).unwrap(),
+                Addon::try_from(&dep2).unwrap(),
+                Addon::try_from(&dep_provider).unwrap(),
+                Addon::try_from(&dep_shared).unwrap()
+            ]
+        );
+    }
+
+    #[rstest]
+    #[case(None, "4.2", ">=4.3, <4.4", false)]
+    #[case(None, "4.3", ">=4.3, <4.4", true)]
+    #[case(Some("4.3.1.stable"), "4.3", ">=4.3, <4.4", true)]
+    #[case(Some("4.4.0"), "4.3", ">=4.3, <4.4", false)]
+    #[case(Some("4.3.2"), "4.2", ">=4.3, <4.4", false)]
+    #[case(Some("4.2.2"), "4.3", ">=4.3, <4.4", false)]
+    #[case(None, "4.2", ">=4.2.1", true)]
+    #[case(None, "4.2", ">4.2.1, <4.2.3", true)]
+    #[case(None, "4.2", "<4.2.0", false)]
+    #[case(Some("4.2"), "4.2", "=4.2.3", true)]
+    #[case(Some("4.2.0"), "4.2", ">=4.2.1", false)]
+    fn test_installer_run_checks_godot_version_requirement(
+        #[case] godot_version: Option<&str>,
+        #[case] features: &str,
+        #[case] godot: &'static str,
+        #[case] want_ok: bool,
+    ) {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: The project's Godot version configuration.
+        std::fs::write(
+            tmp.path().join("project.godot"),
+            format!(
+                "config_version=5\n\n[application]\n\nconfig/features=PackedStringArray(\"{}\", \"Forward Plus\")\n",
+                features
+            ),
+        )
+        .unwrap();
+
+        if let Some(v) = godot_version {
+            std::fs::write(tmp.path().join(".godot-version"), v).unwrap();
+        }
+
+        // Given: A direct dependency which requires a range of Godot versions.
+        let dep = TestDep::builder()
+            .name("1")
+            .addon(DepType::Plugin(Version::new(1, 0, 0)))
+            .godot(Some(godot))
+            .build()
+            .init(tmp.path(), "./1")
+            .unwrap();
+
+        // Given: A root manifest with a direct dependency.
+        let mut m = Manifest::default();
+        m.addons_mut(&Query::prod()).insert(&dep);
+
+        // When: An installation is run for the default target/environment.
+        let got = Install::builder()
+            .manifest(&m)
+            .build()
+            .resolve_addons(tmp.path());
+
+        // Then: The Godot version requirement is validated.
+        match want_ok {
+            true => assert!(got.is_ok()),
+            false => assert!(matches!(got, Err(Error::IncompatibleGodot(..)))),
+        }
+    }
+
+    #[rstest]
+    fn test_installer_run_extension_below_compatibility_minimum_fails() {
+        // Given: A temporary test directory for creating dependencies.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: The project's Godot version configuration.
+        std::fs::write(tmp.path().join(".godot-version"), "4.2.2").unwrap();
+
+        // Given: A direct dependency on an extension requiring Godot v4.3.
+        let dep = TestDep::builder()
+            .name("1")
+            .addon(DepType::Extension)
+            .build()
+            .init(tmp.path(), "./1")
+            .unwrap();
+
+        std::fs::write(
+            tmp.path().join("1/addons/1/1.gdextension"),
+            "[configuration]\ncompatibility_minimum = \"4.3\"\n",
+        )
+        .unwrap();
+
+        // Given: A root manifest with a direct dependency.
+        let mut m = Manifest::default();
+        m.addons_mut(&Query::prod()).insert(&dep);
+
+        // When: An installation is run for the default target/environment.
+        let got = Install::builder()
+            .manifest(&m)
+            .build()
+            .resolve_addons(tmp.path());
+
+        // Then: An error is returned.
+        assert!(matches!(
+            got,
+            Err(Error::IncompatibleGodot(_, _, v, _)) if v == Version::new(4, 2, 2)
+        ));
+    }
+
+    #[rstest]
+    fn test_chain_renders_declared_requirements() {
+        // Given: A dependency on a release declared by the project manifest.
+        let dep = Dependency::builder()
+            .addon(Some(String::from("tween-lib")))
+            .source(Source::Release(
+                crate::git::GitHubRelease::builder()
+                    .tag(String::from("^1"))
+                    .repo(
+                        url::Url::parse("https://github.com/owner/tween-lib")
+                            .map(crate::git::Remote::from)
+                            .unwrap(),
+                    )
+                    .build(),
+            ))
+            .build();
+
+        // When: A chain is built from a path dependency to the release.
+        let chain = Chain::default()
+            .join(Link::new(
+                "ui",
+                &Dependency::builder().source(PathBuf::from("ui")).build(),
+            ))
+            .join(Link::new("tween-lib", &dep));
+
+        // Then: Each link shows the requirement declared for it.
+        assert_eq!(chain.to_string(), "project → ui → tween-lib ^1");
+    }
+
+    #[rstest]
+    #[case(vec!["1"], "the project manifest")]
+    #[case(vec!["2", "1"], "the manifest of '2'")]
+    #[case(vec!["3", "2", "1"], "the manifest of '2'")]
+    fn test_conflict_exact_version_required_names_declaring_manifest(
+        #[case] names: Vec<&str>,
+        #[case] want: &str,
+    ) {
+        // Given: The chain of the addon which requires an exact version.
+        let selected = names.iter().fold(Chain::default(), |c, n| {
+            c.join(Link {
+                name: n.to_string(),
+                requirement: None,
+            })
+        });
+
+        // Given: A conflicting chain with a different requirement.
+        let other = Chain::default().join(Link {
+            name: String::from("1"),
+            requirement: Some(String::from("^1.2")),
+        });
+
+        // When: The conflict is created.
+        let got = Conflict::exact_version_required("1", selected, other);
+
+        // Then: The help text names the manifest which declared the addon.
+        assert_eq!(
+            got.help,
+            format!(
+                "change '1' in {} to a version matching '^1.2' or remove its install \
+                hooks so that another version may be selected",
+                want,
+            )
+        );
+    }
+
+    /* ---------------------------------------------------------------------- */
+    /*                             Struct: TestDep                            */
+    /* ---------------------------------------------------------------------- */
+
+    #[derive(Clone, Debug, TypedBuilder)]
+    struct TestDep {
+        #[builder(default = DepType::Assets)]
+        addon: DepType,
         #[builder(default)]
         deps: Vec<Dependency>,
         #[builder(setter(into))]
         name: String,
+        #[builder(default)]
+        peers: Vec<(&'static str, &'static str)>,
+        #[builder(default)]
+        conflicts: Vec<&'static str>,
+        #[builder(default)]
+        godot: Option<&'static str>,
+        #[builder(default)]
+        provides: Vec<&'static str>,
     }
 
     /* ---------------------------- Enum: DepType --------------------------- */
@@ -755,8 +2479,27 @@ mod tests {
                 }
             };
 
-            if !self.deps.is_empty() {
-                let mut m = Manifest::default();
+            if !self.deps.is_empty()
+                || !self.peers.is_empty()
+                || !self.conflicts.is_empty()
+                || !self.provides.is_empty()
+                || self.godot.is_some()
+            {
+                let mut m = Manifest::parse(
+                    &[
+                        String::from("[project]"),
+                        format!("conflicts = {:?}", self.conflicts),
+                        format!("provides = {:?}", self.provides),
+                        self.godot
+                            .map(|r| format!("godot = {:?}", r))
+                            .unwrap_or_default(),
+                        String::from("[peer-addons]"),
+                    ]
+                    .into_iter()
+                    .chain(self.peers.iter().map(|(n, r)| format!("{} = {:?}", n, r)))
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                )?;
 
                 for dep in &self.deps {
                     m.addons_mut(&Query::prod()).insert(dep);
@@ -22,6 +22,7 @@ pub use hook::Hook;
 mod install;
 
 pub use install::Install;
+pub use install::Resolution;
 
 /* ------------------------------ Mod: project ------------------------------ */
 
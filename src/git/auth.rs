@@ -0,0 +1,214 @@
+use git2::Cred;
+use git2::CredentialType;
+use std::path::PathBuf;
+use url::Url;
+
+use super::Error;
+use super::Remote;
+
+const GIT_USERNAME_SSH: &str = "git";
+const GIT_USERNAME_TOKEN: &str = "x-access-token";
+
+/// The private keys (relative to `~/.ssh`) which are tried, in order, if the
+/// SSH agent fails to authenticate.
+const SSH_DEFAULT_KEYS: &[&str] = &["id_ed25519", "id_ecdsa", "id_rsa"];
+
+/* -------------------------------------------------------------------------- */
+/*                             Function: callbacks                            */
+/* -------------------------------------------------------------------------- */
+
+/// `callbacks` returns the [git2::RemoteCallbacks] used to authenticate with the
+/// [Remote] repository. The following credentials are tried, in order, until
+/// one succeeds:
+///     - SSH: the host's configured `ssh-key`, then the SSH agent, and then the
+///       default private keys in `~/.ssh`.
+///     - HTTP(S): the host's access token (see [token]), then the user's git
+///       credential helpers.
+pub fn callbacks(remote: &Remote) -> Result<git2::RemoteCallbacks<'static>, Error> {
+    let host = remote.host().unwrap_or_default();
+    let settings = super::get_settings()?.host(&host).cloned();
+
+    let username = settings.as_ref().and_then(|s| s.username.clone());
+    let token = token(&host)?;
+
+    // Each SSH key candidate is tried once; `None` represents the SSH agent.
+    let mut keys = settings
+        .and_then(|s| s.ssh_key)
+        .map(|p| Some(expand_home(p)))
+        .into_iter()
+        .chain(std::iter::once(None))
+        .chain(
+            home_dir()
+                .map(|h| {
+                    SSH_DEFAULT_KEYS
+                        .iter()
+                        .map(|k| h.join(".ssh").join(k))
+                        .filter(|p| p.is_file())
+                        .map(Some)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default(),
+        )
+        .collect::<Vec<_>>()
+        .into_iter();
+
+    let mut tried_token = token.is_none();
+    let mut tried_helper = false;
+
+    let mut callbacks = git2::RemoteCallbacks::new();
+    callbacks.credentials(move |url, username_from_url, allowed| {
+        let username = username.as_deref().or(username_from_url);
+
+        if allowed.contains(CredentialType::USERNAME) {
+            return Cred::username(username.unwrap_or(GIT_USERNAME_SSH));
+        }
+
+        if allowed.contains(CredentialType::SSH_KEY) {
+            let username = username.unwrap_or(GIT_USERNAME_SSH);
+
+            match keys.next() {
+                Some(None) => return Cred::ssh_key_from_agent(username),
+                Some(Some(key)) => return Cred::ssh_key(username, None, &key, None),
+                None => {}
+            }
+        }
+
+        if allowed.contains(CredentialType::USER_PASS_PLAINTEXT) {
+            if !tried_token {
+                tried_token = true;
+
+                if let Some(token) = token.as_deref() {
+                    return Cred::userpass_plaintext(username.unwrap_or(GIT_USERNAME_TOKEN), token);
+                }
+            }
+
+            if !tried_helper {
+                tried_helper = true;
+
+                let config = git2::Config::open_default()?;
+                return Cred::credential_helper(&config, url, username);
+            }
+        }
+
+        Err(git2::Error::from_str(&format!(
+            "no valid credentials for repository: {}",
+            url
+        )))
+    });
+
+    Ok(callbacks)
+}
+
+/* -------------------------------------------------------------------------- */
+/*                             Function: authorize                            */
+/* -------------------------------------------------------------------------- */
+
+/// `authorize` adds the access token for the host of `url` (see [token]), if
+/// there is one, to the HTTP request.
+pub fn authorize(
+    request: reqwest::blocking::RequestBuilder,
+    url: &Url,
+) -> Result<reqwest::blocking::RequestBuilder, Error> {
+    let host = canonical_host(url.host_str().unwrap_or_default());
+
+    let token = match token(host)? {
+        None => return Ok(request),
+        Some(t) => t,
+    };
+
+    let username = super::get_settings()?
+        .host(host)
+        .and_then(|s| s.username.clone())
+        .unwrap_or(GIT_USERNAME_TOKEN.to_owned());
+
+    Ok(request.basic_auth(username, Some(token)))
+}
+
+/* -------------------------------------------------------------------------- */
+/*                               Function: token                              */
+/* -------------------------------------------------------------------------- */
+
+/// `token` returns the access token for `host`. The host's `token` setting is
+/// preferred, followed by the well-known environment variable for the host
+/// (e.g. `GITHUB_TOKEN` for `github.com`).
+pub fn token(host: &str) -> Result<Option<String>, Error> {
+    let host = canonical_host(host);
+
+    if let Some(token) = super::get_settings()?
+        .host(host)
+        .and_then(|s| s.token.clone())
+    {
+        return Ok(Some(token));
+    }
+
+    let vars: &[&str] = match host {
+        "github.com" => &["GITHUB_TOKEN", "GH_TOKEN"],
+        "gitlab.com" => &["GITLAB_TOKEN"],
+        "codeberg.org" => &["CODEBERG_TOKEN"],
+        _ => &[],
+    };
+
+    Ok(vars
+        .iter()
+        .find_map(|v| std::env::var(v).ok())
+        .filter(|t| !t.is_empty()))
+}
+
+/* -------------------------------------------------------------------------- */
+/*                          Function: canonical_host                          */
+/* -------------------------------------------------------------------------- */
+
+/// `canonical_host` maps API hosts onto the git host whose credentials they
+/// share.
+fn canonical_host(host: &str) -> &str {
+    match host {
+        "api.github.com" => "github.com",
+        h => h,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                            Function: expand_home                           */
+/* -------------------------------------------------------------------------- */
+
+/// `expand_home` replaces a leading `~` in `path` with the user's home
+/// directory.
+pub(super) fn expand_home(path: PathBuf) -> PathBuf {
+    match (path.strip_prefix("~"), home_dir()) {
+        (Ok(rest), Some(home)) => home.join(rest),
+        _ => path,
+    }
+}
+
+/// `home_dir` returns the user's home directory, if it's defined.
+fn home_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Mod: Tests                                 */
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::expand_home;
+    use super::home_dir;
+
+    /* -------------------------- Test: expand_home ------------------------- */
+
+    #[test]
+    fn test_expand_home_replaces_tilde() {
+        // Given: A path relative to the home directory.
+        let path = PathBuf::from("~/.ssh/id_ed25519");
+
+        // When: The path is expanded.
+        let got = expand_home(path);
+
+        // Then: The home directory is substituted.
+        assert_eq!(got, home_dir().unwrap().join(".ssh/id_ed25519"));
+    }
+}
@@ -1,9 +1,17 @@
+use git2::Oid;
+use std::path::Path;
 use std::path::PathBuf;
+use url::Url;
+use walkdir::WalkDir;
 
 use super::Database;
+use super::Error;
 use super::Reference;
+use super::Remote;
 use super::Source;
 
+pub(super) const CHECKOUT_SPARSE_FILENAME: &str = ".gdpack-sparse";
+
 /* -------------------------------------------------------------------------- */
 /*                             Function: checkout                             */
 /* -------------------------------------------------------------------------- */
@@ -12,11 +20,58 @@ use super::Source;
 /// reference to the version-specific repository.
 pub fn checkout(source: &Source) -> Result<Checkout, super::Error> {
     let db = Database::try_from(source)?;
-    let checkout = db.checkout(source.reference.as_ref())?;
+    let checkout = db.checkout(
+        source.reference.as_ref(),
+        source.submodules.unwrap_or(true),
+        source.verify.as_ref(),
+    )?;
 
     Ok(checkout)
 }
 
+/* -------------------------------------------------------------------------- */
+/*                              Function: include                             */
+/* -------------------------------------------------------------------------- */
+
+/// A helper function for completing a sparse [Checkout] (see [checkout]); all
+/// files at or below `path` are written, including those of any submodules. If
+/// `path` isn't within a sparse [Checkout], then this is a no-op.
+pub fn include(path: impl AsRef<Path>) -> Result<(), super::Error> {
+    let path = path.as_ref();
+
+    let root = match path
+        .ancestors()
+        .find(|p| p.join(CHECKOUT_SPARSE_FILENAME).is_file())
+    {
+        None => return Ok(()),
+        Some(p) => p,
+    };
+
+    let mut sparse = Sparse::read(root)?;
+
+    let path_relative = path.strip_prefix(root).expect("invalid ancestor");
+    if !sparse.include.iter().any(|p| path_relative.starts_with(p)) {
+        let source = Source::builder().repo(sparse.remote.clone()).build();
+
+        Database::try_from(&source)?.include(sparse.id, root, path_relative)?;
+
+        sparse.include.push(path_relative.to_owned());
+        sparse.write(root)?;
+    }
+
+    // Submodules are sparse checkouts of their own, so include them as well.
+    for entry in WalkDir::new(path)
+        .min_depth(2)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|e| e.file_name() == CHECKOUT_SPARSE_FILENAME)
+    {
+        include(entry.path().parent().expect("missing parent directory"))?;
+    }
+
+    Ok(())
+}
+
 /* -------------------------------------------------------------------------- */
 /*                              Struct: Checkout                              */
 /* -------------------------------------------------------------------------- */
@@ -64,3 +119,101 @@ impl Checkout {
         Ok(path)
     }
 }
+
+/* -------------------------------------------------------------------------- */
+/*                               Struct: Sparse                               */
+/* -------------------------------------------------------------------------- */
+
+/// `Sparse` records the state of a sparse [Checkout]; it's stored in a hidden
+/// file at the root of the checkout as the [Remote] repository URL, the commit
+/// id, and then each included path, all on their own line.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub(super) struct Sparse {
+    remote: Remote,
+    id: Oid,
+    include: Vec<PathBuf>,
+}
+
+/* ------------------------------ Impl: Sparse ------------------------------ */
+
+impl Sparse {
+    /* --------------------------- Methods: Public -------------------------- */
+
+    /// Creates a new [Sparse] record for commit `id` with no included paths.
+    pub fn new(remote: &Remote, id: Oid) -> Sparse {
+        Sparse {
+            remote: remote.clone(),
+            id,
+            include: vec![],
+        }
+    }
+
+    /// Reads the [Sparse] record of the checkout at `root`.
+    pub fn read(root: impl AsRef<Path>) -> Result<Sparse, Error> {
+        let path = root.as_ref().join(CHECKOUT_SPARSE_FILENAME);
+        let contents = std::fs::read_to_string(&path).map_err(Error::Io)?;
+
+        let mut lines = contents.lines();
+
+        let remote = lines
+            .next()
+            .ok_or(Error::MissingInput(format!("remote: {}", path.display())))
+            .and_then(|s| Url::parse(s).map_err(Error::Url))?;
+
+        let id = lines
+            .next()
+            .ok_or(Error::MissingInput(format!("revision: {}", path.display())))
+            .and_then(|s| Oid::from_str(s).map_err(Error::Git))?;
+
+        Ok(Sparse {
+            remote: Remote::from(remote),
+            id,
+            include: lines.map(PathBuf::from).collect(),
+        })
+    }
+
+    /// Writes the [Sparse] record into the checkout at `root`.
+    pub fn write(&self, root: impl AsRef<Path>) -> Result<(), Error> {
+        let mut contents = format!("{}\n{}\n", self.remote.url(), self.id);
+        for path in &self.include {
+            contents.push_str(&format!("{}\n", path.display()));
+        }
+
+        std::fs::write(root.as_ref().join(CHECKOUT_SPARSE_FILENAME), contents).map_err(Error::Io)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Mod: Tests                                 */
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use git2::Oid;
+    use std::path::PathBuf;
+    use url::Url;
+
+    use super::Remote;
+    use super::Sparse;
+
+    /* ---------------------------- Test: Sparse ---------------------------- */
+
+    #[test]
+    fn test_sparse_write_then_read_round_trips() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A sparse checkout record with an included path.
+        let remote = Remote::from(Url::parse("https://github.com/owner/repo.git").unwrap());
+        let id = Oid::from_str("0123456789abcdef0123456789abcdef01234567").unwrap();
+
+        let mut sparse = Sparse::new(&remote, id);
+        sparse.include.push(PathBuf::from("addons/addon"));
+
+        // When: The record is written and then read back.
+        sparse.write(tmp.path()).unwrap();
+        let got = Sparse::read(tmp.path()).unwrap();
+
+        // Then: The same record is returned.
+        assert_eq!(got, sparse);
+    }
+}
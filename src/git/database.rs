@@ -1,13 +1,30 @@
 use git2::Oid;
+use sha2::Digest;
+use sha2::Sha256;
 use std::path::Path;
 use std::path::PathBuf;
-use std::process::Command;
 
+use super::auth;
+use super::lfs;
 use super::Checkout;
 use super::Error;
 use super::Reference;
 use super::Remote;
 use super::Source;
+use super::Sparse;
+use super::Verify;
+use crate::config::gdext::Extension;
+use crate::config::godot::ProjectSettings;
+use crate::config::manifest::Manifest;
+use crate::config::plugin::Plugin;
+use crate::config::Configuration;
+
+const GIT_MODULES_FILENAME: &str = ".gitmodules";
+const STORE_ID_HASH_LEN: usize = 8;
+
+/// The fetch depth which `libgit2` interprets as a request to fetch the
+/// complete history of a shallow repository.
+const GIT_FETCH_DEPTH_UNSHALLOW: i32 = i32::MAX;
 
 /* -------------------------------------------------------------------------- */
 /*                              Struct: Database                              */
@@ -25,19 +42,45 @@ impl Database {
     /* --------------------------- Methods: Public -------------------------- */
 
     /// Checks out the specific [Reference] into the appropriate "checkout"
-    /// directory in the `gdpack` store.
-    pub fn checkout(&self, reference: Option<&Reference>) -> Result<Checkout, Error> {
+    /// directory in the `gdpack` store. If `submodules` is set, then any git
+    /// submodules of the revision are recursively initialized as well. If
+    /// `verify` is set, then the tag (or, for other references, the commit)
+    /// must be signed by one of its trusted keys.
+    pub fn checkout(
+        &self,
+        reference: Option<&Reference>,
+        submodules: bool,
+        verify: Option<&Verify>,
+    ) -> Result<Checkout, Error> {
         let path_db = Database::get_path(&self.0)?;
 
         let repo = git2::Repository::open(&path_db).map_err(Error::Git)?;
 
-        let obj = repo
-            .revparse_single(
-                &reference
-                    .map(Reference::to_string)
-                    .unwrap_or(String::from("HEAD")),
-            )
-            .map_err(Error::Git)?;
+        let obj = self.resolve(&repo, reference)?;
+
+        if let Some(verify) = verify {
+            let spec = reference
+                .map(Reference::to_string)
+                .unwrap_or(String::from("HEAD"));
+
+            let id = match (reference, obj.as_tag()) {
+                (Some(Reference::Tag(_)), Some(tag)) => tag.id(),
+                (Some(Reference::Tag(_)), None) => {
+                    return Err(Error::Signature(
+                        spec,
+                        "lightweight tags can't be signed".into(),
+                    ))
+                }
+                _ => obj.peel(git2::ObjectType::Commit).map_err(Error::Git)?.id(),
+            };
+
+            verify
+                .trusted()?
+                .verify_object(&repo, id)
+                .map_err(|e| Error::Signature(spec, e))?;
+        }
+
+        let obj = obj.peel(git2::ObjectType::Commit).map_err(Error::Git)?;
 
         let source = &Source::builder()
             .reference(Some(Reference::Rev(obj.id().to_string())))
@@ -46,6 +89,9 @@ impl Database {
 
         let path_checkout = Checkout::get_path(&repo, source)?;
         if !path_checkout.exists() {
+            // NOTE: The revision was already resolved to a local commit, so a
+            // failed fetch (which was already retried) isn't fatal; this keeps
+            // cached revisions installable while offline.
             if let Err(e) = self.fetch_latest(reference) {
                 println!("failed to fetch latest: {}; skipping...", e);
             }
@@ -59,20 +105,13 @@ impl Database {
                 self.0.name().expect("missing remote name")
             );
 
-            let repo =
-                git2::Repository::clone(path_db.to_str().expect("invalid path"), &path_checkout)
-                    .map_err(Error::Git)?;
-
-            repo.checkout_tree(
-                &repo
-                    .find_commit(Oid::from_str(&obj.id().to_string()).map_err(Error::Git)?)
-                    .map_err(Error::Git)?
-                    .into_object(),
-                Some(git2::build::CheckoutBuilder::new().force().refresh(true)),
-            )
-            .map_err(Error::Git)?;
+            self.export(obj.id(), &path_checkout)?;
+        }
 
-            repo.set_head_detached(obj.id()).unwrap();
+        // NOTE: This is run even if the checkout already exists so that any
+        // missing submodules are initialized if the option was changed.
+        if submodules {
+            init_submodules(&self.0, obj.id(), &path_checkout)?;
         }
 
         let checkout = Checkout {
@@ -95,27 +134,156 @@ impl Database {
 
         let repo = git2::Repository::open(path).map_err(Error::Git)?;
 
-        let mut remote = repo
-            .remote_anonymous(&self.0.to_string())
-            .map_err(Error::Git)?;
-
-        remote
-            .fetch(
-                &Reference::refspecs(reference),
-                Some(
-                    git2::FetchOptions::default()
-                        .prune(git2::FetchPrune::On)
-                        .update_fetchhead(true),
-                ),
-                None,
-            )
-            .map_err(Error::Git)?;
+        super::with_mirrors(self.0.url(), |url| {
+            let remote = Remote::from(url.clone());
 
-        Ok(())
+            let mut opts = super::get_fetch_options(&remote)?;
+            opts.prune(git2::FetchPrune::On).update_fetchhead(true);
+
+            // Keep a shallow "database" shallow; its history is only deepened
+            // on demand (see [Database::resolve]).
+            if repo.is_shallow() {
+                opts.depth(1);
+            }
+
+            repo.remote_anonymous(&remote.to_string())
+                .and_then(|mut r| r.fetch(&Reference::refspecs(reference), Some(&mut opts), None))
+                .map_err(Error::Git)
+        })
     }
 
     /* -------------------------- Methods: Private -------------------------- */
 
+    /// Resolves the [Reference] within the "database" bare clone `repo`. If the
+    /// clone is shallow and the revision isn't present, then the revision is
+    /// fetched directly. If that fails, then the clone's history is deepened
+    /// completely before trying again.
+    fn resolve<'a>(
+        &self,
+        repo: &'a git2::Repository,
+        reference: Option<&Reference>,
+    ) -> Result<git2::Object<'a>, Error> {
+        let spec = reference
+            .map(Reference::to_string)
+            .unwrap_or(String::from("HEAD"));
+
+        match repo.revparse_single(&spec) {
+            Ok(obj) => return Ok(obj),
+            Err(e) if !repo.is_shallow() => return Err(Error::Git(e)),
+            Err(_) => (),
+        }
+
+        // First, try fetching only the requested revision. Note that servers
+        // may reject requests for commits which aren't advertised, in which
+        // case the error is ignored.
+        let refspecs = match reference {
+            None => vec![],
+            Some(Reference::Branch(b)) => vec![format!("+refs/heads/{0}:refs/heads/{0}", b)],
+            Some(Reference::Tag(t)) => vec![format!("+refs/tags/{0}:refs/tags/{0}", t)],
+            Some(Reference::Rev(r)) => {
+                if r.starts_with("refs/") {
+                    vec![format!("+{0}:{0}", r)]
+                } else if r.len() == 40 && r.chars().all(|c| c.is_ascii_hexdigit()) {
+                    vec![format!("+{0}:refs/commit/{0}", r)]
+                } else {
+                    vec![]
+                }
+            }
+        };
+
+        if !refspecs.is_empty() {
+            println!(
+                "fetching revision {} for dependency: {}",
+                spec,
+                self.0.name().expect("missing remote name")
+            );
+
+            if fetch(&self.0, repo, &refspecs, 1).is_ok() {
+                if let Ok(obj) = repo.revparse_single(&spec) {
+                    return Ok(obj);
+                }
+            }
+        }
+
+        // Otherwise, fetch the complete history of all branches and tags.
+        println!(
+            "deepening history for dependency: {}",
+            self.0.name().expect("missing remote name")
+        );
+
+        fetch(
+            &self.0,
+            repo,
+            &["+refs/heads/*:refs/heads/*", "+refs/tags/*:refs/tags/*"],
+            GIT_FETCH_DEPTH_UNSHALLOW,
+        )?;
+
+        repo.revparse_single(&spec).map_err(Error::Git)
+    }
+
+    /// Exports a sparse checkout of commit `id` into the directory `path`. Only
+    /// the directory structure and the files needed to discover addons are
+    /// written; the remaining files are written on demand by
+    /// [Database::include]. If this fails, then `path` is removed so that a
+    /// partial checkout isn't reused.
+    fn export(&self, id: Oid, path: impl AsRef<Path>) -> Result<(), Error> {
+        let path = path.as_ref();
+
+        let result = self
+            .open()
+            .and_then(|repo| {
+                let tree = find_tree(&repo, id)?;
+                write_tree(&repo, &tree, path, &is_discovery_file)
+            })
+            .and_then(|_| Sparse::new(&self.0, id).write(path));
+
+        if result.is_err() && path.exists() {
+            std::fs::remove_dir_all(path).map_err(Error::Io)?;
+        }
+
+        result
+    }
+
+    /// Writes all files under the relative path `path` of commit `id` into the
+    /// sparse checkout rooted at `root`, including any Git LFS objects. If the
+    /// commit doesn't contain `path` (e.g. a library built after checkout),
+    /// then nothing is written.
+    pub(super) fn include(&self, id: Oid, root: &Path, path: &Path) -> Result<(), Error> {
+        let repo = self.open()?;
+        let tree = find_tree(&repo, id)?;
+
+        let target = root.join(path);
+
+        if path.as_os_str().is_empty() {
+            write_tree(&repo, &tree, &target, &|_| true)?;
+        } else {
+            let entry = match tree.get_path(path) {
+                Ok(entry) => entry,
+                Err(e) if e.code() == git2::ErrorCode::NotFound => return Ok(()),
+                Err(e) => return Err(Error::Git(e)),
+            };
+
+            match entry.kind() {
+                Some(git2::ObjectType::Tree) => {
+                    let tree = repo.find_tree(entry.id()).map_err(Error::Git)?;
+                    write_tree(&repo, &tree, &target, &|_| true)?;
+                }
+                Some(git2::ObjectType::Blob) => {
+                    let blob = repo.find_blob(entry.id()).map_err(Error::Git)?;
+                    write_blob(&blob, entry.filemode(), &target)?;
+                }
+                _ => return Ok(()),
+            }
+        }
+
+        lfs::pull(&self.0, root, target)
+    }
+
+    /// Opens the "database" bare clone.
+    fn open(&self) -> Result<git2::Repository, Error> {
+        git2::Repository::open(Database::get_path(&self.0)?).map_err(Error::Git)
+    }
+
     /// Returns a path to the "database" bare clone for the specified [Remote] in
     /// the `gdpack` store.
     pub(super) fn get_path(remote: &Remote) -> Result<PathBuf, Error> {
@@ -126,26 +294,66 @@ impl Database {
     }
 
     /// Returns the directory name for the "database" bare clone for the specified
-    /// [Remote] in the `gdpack` store.
+    /// [Remote] in the `gdpack` store. Local repositories, which have no host,
+    /// are identified by their path instead.
+    ///
+    /// NOTE: Flattening a multi-segment path into a directory name is lossy
+    /// (e.g. `a_b/c` and `a/b_c`), so the ids of local repositories and of
+    /// nested namespaces end with a short hash of the full remote path. Other
+    /// ids are left unchanged so that existing store entries remain valid.
     pub(super) fn id(remote: &Remote) -> Result<String, Error> {
+        if remote.is_local() && remote.host().is_none() {
+            let segments = remote
+                .url()
+                .path_segments()
+                .map(|s| s.filter(|s| !s.is_empty()).collect::<Vec<_>>())
+                .filter(|s| !s.is_empty())
+                .ok_or(Error::MissingInput(format!("repository path: {}", remote)))?;
+
+            let path = segments.join("_");
+            let path = path.strip_suffix(".git").unwrap_or(&path);
+
+            return Ok(format!(
+                "file_{}_{}",
+                path.replace(['.', ':'], "_").to_lowercase(),
+                hash_id(&segments.join("/")),
+            ));
+        }
+
         let host = remote
             .host()
-            .map(|s| s.replace('.', "_"))
             .map(|s| s.to_lowercase())
             .ok_or(Error::MissingInput(format!("repository host: {}", remote)))?;
 
-        let owner = remote
-            .owner()
-            .map(|s| s.replace('/', "_"))
+        let namespace = remote
+            .namespace()
             .map(|s| s.to_lowercase())
-            .ok_or(Error::MissingInput(format!("repository owner: {}", remote)))?;
+            .ok_or(Error::MissingInput(format!(
+                "repository namespace: {}",
+                remote
+            )))?;
 
         let name = remote
             .name()
             .map(|s| s.to_lowercase())
             .ok_or(Error::MissingInput(format!("repository name: {}", remote)))?;
 
-        Ok(format!("{}_{}_{}", host, owner, name))
+        let id = format!(
+            "{}_{}_{}",
+            host.replace('.', "_"),
+            namespace.replace('/', "_"),
+            name,
+        );
+
+        if !namespace.contains('/') {
+            return Ok(id);
+        }
+
+        Ok(format!(
+            "{}_{}",
+            id,
+            hash_id(&format!("{}/{}/{}", host, namespace, name))
+        ))
     }
 }
 
@@ -166,39 +374,435 @@ impl TryFrom<&Source> for Database {
     }
 }
 
+/* ---------------------------- Function: hash_id --------------------------- */
+
+/// Returns a short, stable hash of `key` used to disambiguate store ids.
+fn hash_id(key: &str) -> String {
+    format!("{:x}", Sha256::digest(key.as_bytes()))[..STORE_ID_HASH_LEN].to_owned()
+}
+
+/* ------------------------ Function: init_submodules ----------------------- */
+
+/// Recursively initializes the git submodules of the checkout at `path`, which
+/// was exported from commit `id` of the [Remote] `remote`. Each submodule
+/// repository is cached in the `gdpack` store as its own [Database].
+fn init_submodules(remote: &Remote, id: Oid, path: impl AsRef<Path>) -> Result<(), Error> {
+    let path = path.as_ref();
+
+    let path_gitmodules = path.join(GIT_MODULES_FILENAME);
+    if !path_gitmodules.is_file() {
+        return Ok(());
+    }
+
+    let repo = git2::Repository::open(Database::get_path(remote)?).map_err(Error::Git)?;
+    let tree = find_tree(&repo, id)?;
+
+    // Collect the name and path of each submodule declared in '.gitmodules'.
+    let config = git2::Config::open(&path_gitmodules).map_err(Error::Git)?;
+
+    let mut submodules = Vec::<(String, PathBuf)>::new();
+    config
+        .entries(Some(r"submodule\..*\.path"))
+        .and_then(|entries| {
+            entries.for_each(|e| {
+                let name = e
+                    .name()
+                    .and_then(|n| n.strip_prefix("submodule."))
+                    .and_then(|n| n.strip_suffix(".path"));
+
+                if let (Some(name), Some(path)) = (name, e.value()) {
+                    submodules.push((name.to_owned(), PathBuf::from(path)));
+                }
+            })
+        })
+        .map_err(Error::Git)?;
+
+    for (name, path_relative) in submodules {
+        // The commit of the submodule is recorded in the parent's tree.
+        let id = match tree.get_path(&path_relative) {
+            Ok(e) if e.kind() == Some(git2::ObjectType::Commit) => e.id(),
+            _ => continue, // Not tracked by the checked out revision.
+        };
+
+        let url = config
+            .get_string(&format!("submodule.{}.url", name))
+            .map_err(|_| Error::MissingInput(format!("submodule url: {}", name)))?;
+
+        let remote = remote.join(&url)?;
+        let path_submodule = path.join(&path_relative);
+
+        let is_empty = std::fs::read_dir(&path_submodule)
+            .map(|mut d| d.next().is_none())
+            .unwrap_or(true);
+
+        if is_empty {
+            println!(
+                "installing submodule {} for dependency: {}",
+                path_relative.display(),
+                remote,
+            );
+
+            let source = Source::builder()
+                .reference(Some(Reference::Rev(id.to_string())))
+                .repo(remote.clone())
+                .build();
+
+            let db = Database::try_from(&source)?;
+
+            // The submodule's commit may not be reachable from any of the
+            // branches fetched during the initial clone.
+            let repo_db =
+                git2::Repository::open(Database::get_path(&remote)?).map_err(Error::Git)?;
+            db.resolve(&repo_db, source.reference.as_ref())?;
+
+            db.export(id, &path_submodule)?;
+        }
+
+        init_submodules(&remote, id, &path_submodule)?;
+    }
+
+    Ok(())
+}
+
+/* -------------------------- Function: write_tree -------------------------- */
+
+/// Writes the contents of `tree` into the directory `path`. Every directory is
+/// written, but only the files for which `filter` returns `true` are. Submodules
+/// are written as empty directories (see [init_submodules]).
+fn write_tree(
+    repo: &git2::Repository,
+    tree: &git2::Tree,
+    path: &Path,
+    filter: &dyn Fn(&Path) -> bool,
+) -> Result<(), Error> {
+    std::fs::create_dir_all(path).map_err(Error::Io)?;
+
+    for entry in tree.iter() {
+        let name = entry.name().ok_or(Error::InvalidInput(format!(
+            "tree entry: {:?}",
+            entry.name_bytes()
+        )))?;
+
+        let target = path.join(name);
+
+        match entry.kind() {
+            Some(git2::ObjectType::Tree) => {
+                let tree = repo.find_tree(entry.id()).map_err(Error::Git)?;
+                write_tree(repo, &tree, &target, filter)?;
+            }
+            Some(git2::ObjectType::Commit) => {
+                std::fs::create_dir_all(&target).map_err(Error::Io)?;
+            }
+            Some(git2::ObjectType::Blob) if filter(&target) => {
+                let blob = repo.find_blob(entry.id()).map_err(Error::Git)?;
+                write_blob(&blob, entry.filemode(), &target)?;
+            }
+            _ => continue,
+        }
+    }
+
+    Ok(())
+}
+
+/* -------------------------- Function: write_blob -------------------------- */
+
+/// Writes the contents of `blob` to the file at `path`, respecting the
+/// executable and symbolic link modes stored by git (where supported).
+fn write_blob(blob: &git2::Blob, mode: i32, path: &Path) -> Result<(), Error> {
+    // A previously written file (e.g. one needed for discovery) may be replaced.
+    if path.symlink_metadata().is_ok() {
+        std::fs::remove_file(path).map_err(Error::Io)?;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+        use std::os::unix::fs::PermissionsExt;
+
+        if mode == i32::from(git2::FileMode::Link) {
+            let target = std::ffi::OsStr::from_bytes(blob.content());
+            return std::os::unix::fs::symlink(target, path).map_err(Error::Io);
+        }
+
+        std::fs::write(path, blob.content()).map_err(Error::Io)?;
+
+        if mode == i32::from(git2::FileMode::BlobExecutable) {
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))
+                .map_err(Error::Io)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = mode;
+
+        std::fs::write(path, blob.content()).map_err(Error::Io)
+    }
+}
+
+/* ----------------------- Function: is_discovery_file ---------------------- */
+
+/// Returns whether the file at `path` is needed to discover the addons within a
+/// checkout (see [crate::core::Addon::find_in_dir]) or to initialize it.
+fn is_discovery_file(path: &Path) -> bool {
+    Plugin::matches(path)
+        || Extension::matches(path)
+        || Manifest::matches(path)
+        || ProjectSettings::matches(path)
+        || path
+            .file_name()
+            .is_some_and(|n| n == GIT_MODULES_FILENAME || n == lfs::LFS_CONFIG_FILENAME)
+}
+
+/* --------------------------- Function: find_tree -------------------------- */
+
+/// Returns the tree of commit `id` within the repository `repo`.
+fn find_tree(repo: &git2::Repository, id: Oid) -> Result<git2::Tree<'_>, Error> {
+    repo.find_commit(id)
+        .and_then(|c| c.tree())
+        .map_err(Error::Git)
+}
+
+/* ----------------------------- Function: fetch ---------------------------- */
+
+/// Fetches the provided `refspecs` from the [Remote] (or one of its mirrors)
+/// into the repository `repo`, limiting the fetched history to `depth` commits.
+fn fetch(
+    remote: &Remote,
+    repo: &git2::Repository,
+    refspecs: &[impl AsRef<str>],
+    depth: i32,
+) -> Result<(), Error> {
+    let refspecs = refspecs.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+
+    super::with_mirrors(remote.url(), |url| {
+        let remote = Remote::from(url.clone());
+
+        let mut opts = super::get_fetch_options(&remote)?;
+        opts.depth(depth);
+
+        repo.remote_anonymous(&remote.to_string())
+            .and_then(|mut r| r.fetch(&refspecs, Some(&mut opts), None))
+            .map_err(|e| Error::Fetch(remote.to_string(), e))
+    })
+}
+
 /* -------------------------- Function: clone_bare -------------------------- */
 
 /// Bare clones the provided repository, specified by [Source], into the
-/// appropriate "database" directory in the `gdpack` store.
+/// appropriate "database" directory in the `gdpack` store. Unless the
+/// repository is local, only the latest commit of each branch is fetched;
+/// history is fetched on demand by [Database::resolve]. If the clone fails,
+/// then `path` is removed so that a partial clone isn't reused. The repository
+/// is cloned from the first of its mirrors which succeeds, but its "origin" is
+/// always the canonical remote.
 fn clone_bare(source: &Source, path: impl AsRef<Path>) -> Result<(), Error> {
     println!("downloading dependency: {}", source.repo);
 
-    std::fs::create_dir_all(&path).map_err(Error::Io)?;
+    let path = path.as_ref();
 
-    let mut clone_cmd = Command::new("git");
+    super::with_mirrors(source.repo.url(), |url| {
+        clone_bare_from(&Remote::from(url.clone()), &source.repo, path)
+    })
+}
 
-    clone_cmd
-        .current_dir(path.as_ref())
-        .arg("clone")
-        .arg(source.repo.to_string())
-        .arg("--no-checkout");
+/// Bare clones the repository `canonical` from the URL of `remote` (i.e. the
+/// canonical remote or one of its mirrors) into `path`.
+fn clone_bare_from(remote: &Remote, canonical: &Remote, path: &Path) -> Result<(), Error> {
+    let mut opts = super::get_fetch_options(remote)?;
+    opts.download_tags(git2::AutotagOption::All);
 
-    if let Some(Reference::Tag(_)) = source.reference {
-        clone_cmd.arg("--tags");
+    // NOTE: The local transport doesn't support shallow fetches, which are of
+    // little benefit for a repository on the same filesystem anyway.
+    if !remote.is_local() {
+        opts.depth(1);
     }
 
-    let output = clone_cmd
-        .args(["--bare", path.as_ref().to_str().unwrap()])
-        .output()
-        .map_err(Error::Io)?;
-
-    if !output.status.success() {
-        if let Ok(s) = std::str::from_utf8(&output.stderr) {
-            if !s.is_empty() {
-                println!("{}", s);
-            }
+    let callbacks = auth::callbacks(remote)?;
+    let proxy = super::get_proxy_options()?;
+
+    // NOTE: Unlike a non-bare clone, branches are stored directly under
+    // 'refs/heads' so that they can be resolved by name (matching 'git clone
+    // --bare').
+    let result = git2::build::RepoBuilder::new()
+        .bare(true)
+        .fetch_options(opts)
+        .remote_create(|repo, name, url| {
+            repo.remote_with_fetch(name, url, "+refs/heads/*:refs/heads/*")
+        })
+        .clone(&remote.to_string(), path)
+        .and_then(|repo| {
+            // Because of the custom refspec, 'libgit2' can't determine the
+            // remote's default branch, so 'HEAD' needs to be set explicitly.
+            let mut remote = repo.find_remote("origin")?;
+            remote.connect_auth(git2::Direction::Fetch, Some(callbacks), Some(proxy))?;
+
+            let branch = remote.default_branch()?;
+            let branch = branch
+                .as_str()
+                .ok_or(git2::Error::from_str("invalid default branch"))?;
+
+            repo.set_head(branch)?;
+
+            repo.remote_set_url("origin", &canonical.to_string())
+        });
+
+    if let Err(e) = result {
+        if path.exists() {
+            std::fs::remove_dir_all(path).map_err(Error::Io)?;
         }
+
+        return Err(Error::Clone(remote.to_string(), e));
     }
 
     Ok(())
 }
+
+/* -------------------------------------------------------------------------- */
+/*                                 Mod: Tests                                 */
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::is_discovery_file;
+    use super::write_tree;
+    use super::Database;
+
+    /* ------------------------- Test: Database::id ------------------------- */
+
+    #[rstest]
+    #[case("https://github.com/Owner/Repo")]
+    #[case("https://github.com/owner/repo.git/")]
+    #[case("git@github.com:owner/repo.git")]
+    #[case("ssh://git@github.com:22/owner/repo")]
+    fn test_database_id_normalizes_remote(#[case] input: &str) {
+        // Given: A remote spelled in one of several equivalent ways.
+        let remote = input.parse().unwrap();
+
+        // When: The store id of the remote is determined.
+        let got = Database::id(&remote).unwrap();
+
+        // Then: Each spelling shares the same store id.
+        assert_eq!(got, "github_com_owner_repo");
+    }
+
+    #[test]
+    fn test_database_id_of_local_repository_uses_path() {
+        // Given: A remote on the local filesystem.
+        let remote = "file:///srv/git/Addon.git".parse().unwrap();
+
+        // When: The store id of the remote is determined.
+        let got = Database::id(&remote).unwrap();
+
+        // Then: The id is derived from the repository's path.
+        assert_eq!(got, "file_srv_git_addon_7cdd0725");
+    }
+
+    #[test]
+    fn test_database_id_includes_nested_namespace() {
+        // Given: A remote within a nested namespace (e.g. a GitLab subgroup).
+        let remote = "https://gitlab.com/studio/tools/godot-ui.git"
+            .parse()
+            .unwrap();
+
+        // When: The store id of the remote is determined.
+        let got = Database::id(&remote).unwrap();
+
+        // Then: The id includes the full namespace and the final segment.
+        assert_eq!(got, "gitlab_com_studio_tools_godot-ui_c5825478");
+    }
+
+    #[rstest]
+    #[case("file:///srv/git_addon", "file:///srv/git/addon")]
+    #[case("https://gitlab.com/a_b/c/addon", "https://gitlab.com/a/b_c/addon")]
+    fn test_database_id_distinguishes_flattened_paths(#[case] a: &str, #[case] b: &str) {
+        // Given: Two remotes whose paths flatten to the same directory name.
+        let (a, b) = (a.parse().unwrap(), b.parse().unwrap());
+
+        // When: The store ids of the remotes are determined.
+        let got = (Database::id(&a).unwrap(), Database::id(&b).unwrap());
+
+        // Then: Each remote has its own store id.
+        assert_ne!(got.0, got.1);
+    }
+
+    /* -------------------------- Test: write_tree -------------------------- */
+
+    #[test]
+    fn test_write_tree_exports_files_without_metadata() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A repository with a nested file and a submodule entry.
+        let repo = git2::Repository::init_bare(tmp.path().join("repo")).unwrap();
+
+        let blob = repo.blob(b"contents").unwrap();
+
+        let mut builder = repo.treebuilder(None).unwrap();
+        builder
+            .insert("file.txt", blob, git2::FileMode::Blob.into())
+            .unwrap();
+        let subtree = builder.write().unwrap();
+
+        let mut builder = repo.treebuilder(None).unwrap();
+        builder
+            .insert("addon", subtree, git2::FileMode::Tree.into())
+            .unwrap();
+        builder
+            .insert("vendor", blob, git2::FileMode::Commit.into())
+            .unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+
+        // When: The tree is written to a directory.
+        let path = tmp.path().join("out");
+        write_tree(&repo, &tree, &path, &|_| true).unwrap();
+
+        // Then: Only the tree's contents are written.
+        assert_eq!(
+            std::fs::read_to_string(path.join("addon/file.txt")).unwrap(),
+            "contents"
+        );
+        assert!(path.join("vendor").is_dir());
+        assert!(!path.join(".git").exists());
+    }
+
+    #[test]
+    fn test_write_tree_sparse_exports_only_discovery_files() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A repository with an addon in a nested directory.
+        let repo = git2::Repository::init_bare(tmp.path().join("repo")).unwrap();
+
+        let blob = repo.blob(b"contents").unwrap();
+
+        let mut builder = repo.treebuilder(None).unwrap();
+        builder
+            .insert("plugin.cfg", blob, git2::FileMode::Blob.into())
+            .unwrap();
+        builder
+            .insert("plugin.gd", blob, git2::FileMode::Blob.into())
+            .unwrap();
+        let subtree = builder.write().unwrap();
+
+        let mut builder = repo.treebuilder(None).unwrap();
+        builder
+            .insert("addon", subtree, git2::FileMode::Tree.into())
+            .unwrap();
+        builder
+            .insert("README.md", blob, git2::FileMode::Blob.into())
+            .unwrap();
+        let tree = repo.find_tree(builder.write().unwrap()).unwrap();
+
+        // When: The tree is written with the discovery filter.
+        let path = tmp.path().join("out");
+        write_tree(&repo, &tree, &path, &is_discovery_file).unwrap();
+
+        // Then: Only the directories and discovery files are written.
+        assert!(path.join("addon/plugin.cfg").is_file());
+        assert!(!path.join("addon/plugin.gd").exists());
+        assert!(!path.join("README.md").exists());
+    }
+}
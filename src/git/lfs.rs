@@ -0,0 +1,527 @@
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use url::Url;
+use walkdir::WalkDir;
+
+use super::auth;
+use super::checkout::CHECKOUT_SPARSE_FILENAME;
+use super::Error;
+use super::Remote;
+
+const ENV_GDPACK_LFS_URL: &str = "GDPACK_LFS_URL";
+pub(super) const LFS_CONFIG_FILENAME: &str = ".lfsconfig";
+const LFS_MEDIA_TYPE: &str = "application/vnd.git-lfs+json";
+const LFS_OBJECTS_PATHS: [&str; 2] = ["lfs/objects", ".git/lfs/objects"];
+const LFS_POINTER_MAX_SIZE: u64 = 1024;
+const LFS_POINTER_VERSION: &str = "version https://git-lfs.github.com/spec/v1";
+
+/* -------------------------------------------------------------------------- */
+/*                               Function: pull                               */
+/* -------------------------------------------------------------------------- */
+
+/// `pull` replaces each Git LFS pointer file at or below `path`, within the
+/// checkout at `root`, with the object it points to. Objects are downloaded
+/// from the LFS server of the [Remote] repository (see [endpoint]), or copied
+/// from a local repository, and cached in the `gdpack` store so that they're
+/// shared across checkouts.
+///
+/// NOTE: Submodules are checkouts of other repositories, so they're skipped;
+/// their objects are pulled when each submodule is included.
+pub fn pull(remote: &Remote, root: impl AsRef<Path>, path: impl AsRef<Path>) -> Result<(), Error> {
+    let path = path.as_ref();
+
+    let mut pointers = HashMap::<PathBuf, Pointer>::new();
+
+    for entry in WalkDir::new(path)
+        .into_iter()
+        .filter_entry(|e| {
+            // Skip git metadata and any submodule checkouts.
+            e.file_name() != ".git"
+                && (e.depth() == 0 || !e.path().join(CHECKOUT_SPARSE_FILENAME).is_file())
+        })
+        .filter_map(Result::ok)
+        .filter(|e| e.file_type().is_file())
+    {
+        if let Some(pointer) = Pointer::read(entry.path())? {
+            pointers.insert(entry.path().to_owned(), pointer);
+        }
+    }
+
+    if pointers.is_empty() {
+        return Ok(());
+    }
+
+    let mut missing = Vec::<&Pointer>::new();
+    for pointer in pointers.values() {
+        if !pointer.get_path()?.is_file() && !missing.contains(&pointer) {
+            missing.push(pointer);
+        }
+    }
+
+    if !missing.is_empty() {
+        println!(
+            "downloading {} LFS object(s) for dependency: {}",
+            missing.len(),
+            remote
+        );
+
+        match endpoint(remote, root)? {
+            Some(url) => download(&url, &missing)?,
+            None => copy_local(remote, &missing)?,
+        }
+    }
+
+    for (path_file, pointer) in pointers {
+        let path_object = pointer.get_path()?;
+
+        // Leave the pointer file in place if its object couldn't be found.
+        if !path_object.is_file() {
+            continue;
+        }
+
+        std::fs::remove_file(&path_file).map_err(Error::Io)?;
+
+        // Prefer sharing the cached object, but fall back to a copy in case
+        // the store is on another filesystem.
+        if std::fs::hard_link(&path_object, &path_file).is_err() {
+            std::fs::copy(&path_object, &path_file).map_err(Error::Io)?;
+        }
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+/*                               Struct: Pointer                              */
+/* -------------------------------------------------------------------------- */
+
+/// A parsed Git LFS [pointer file](https://github.com/git-lfs/git-lfs/blob/main/docs/spec.md),
+/// which stands in for the actual file contents within a git repository.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize)]
+pub struct Pointer {
+    pub oid: String,
+    pub size: u64,
+}
+
+/* ------------------------------ Impl: Pointer ----------------------------- */
+
+impl Pointer {
+    /* --------------------------- Methods: Public -------------------------- */
+
+    /// Parses the provided file `contents` into a [Pointer], returning `None`
+    /// if the contents aren't a valid pointer file.
+    pub fn parse(contents: &str) -> Option<Pointer> {
+        let mut lines = contents.lines();
+
+        if lines.next()? != LFS_POINTER_VERSION {
+            return None;
+        }
+
+        let mut oid = None;
+        let mut size = None;
+
+        for line in lines {
+            match line.split_once(' ') {
+                Some(("oid", v)) => {
+                    oid = v
+                        .strip_prefix("sha256:")
+                        .filter(|s| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit()))
+                        .map(str::to_lowercase)
+                }
+                Some(("size", v)) => size = v.parse::<u64>().ok(),
+                _ => continue, // Ignore extensions and unknown keys.
+            }
+        }
+
+        Some(Pointer {
+            oid: oid?,
+            size: size?,
+        })
+    }
+
+    /// Reads the file at `path`, returning a [Pointer] if it's a pointer file.
+    pub fn read(path: impl AsRef<Path>) -> Result<Option<Pointer>, Error> {
+        let path = path.as_ref();
+
+        if std::fs::metadata(path).map_err(Error::Io)?.len() >= LFS_POINTER_MAX_SIZE {
+            return Ok(None);
+        }
+
+        let contents = std::fs::read(path).map_err(Error::Io)?;
+
+        Ok(std::str::from_utf8(&contents).ok().and_then(Pointer::parse))
+    }
+
+    /* -------------------------- Methods: Private -------------------------- */
+
+    /// Verifies that `contents` are the object described by the [Pointer] and
+    /// then saves them into the `gdpack` store.
+    fn store(&self, contents: &[u8]) -> Result<(), Error> {
+        if contents.len() as u64 != self.size
+            || format!("{:x}", Sha256::digest(contents)) != self.oid
+        {
+            return Err(Error::Lfs(format!("checksum mismatch: {}", self.oid)));
+        }
+
+        // Write the object atomically so that an interrupted write can't leave
+        // a corrupt object in the store.
+        let path = self.get_path()?;
+        let dir = path.parent().expect("missing parent directory");
+
+        std::fs::create_dir_all(dir).map_err(Error::Io)?;
+
+        let mut file = tempfile::NamedTempFile::new_in(dir).map_err(Error::Io)?;
+        file.write_all(contents).map_err(Error::Io)?;
+        file.persist(&path).map_err(|e| Error::Io(e.error))?;
+
+        Ok(())
+    }
+
+    /// Returns a path to the cached object in the `gdpack` store.
+    fn get_path(&self) -> Result<PathBuf, Error> {
+        let mut path = super::get_store_path()?;
+        path.extend(&[
+            "lfs",
+            "objects",
+            &self.oid[0..2],
+            &self.oid[2..4],
+            &self.oid,
+        ]);
+
+        Ok(path)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                             Function: endpoint                             */
+/* -------------------------------------------------------------------------- */
+
+/// `endpoint` returns the LFS server [Url] for the checkout at `path`. It's
+/// determined by the following, in order:
+///     1. The `GDPACK_LFS_URL` environment variable (useful for testing).
+///     2. The `lfs.url` property of the checkout's `.lfsconfig` file.
+///     3. The default LFS server of the [Remote] repository, which is the
+///        `https` repository URL with `.git/info/lfs` appended.
+///
+/// A local [Remote] repository has no default LFS server, so `None` is
+/// returned for it instead (see [copy_local]).
+fn endpoint(remote: &Remote, path: impl AsRef<Path>) -> Result<Option<Url>, Error> {
+    if let Ok(url) = std::env::var(ENV_GDPACK_LFS_URL) {
+        return Url::parse(&url).map(Some).map_err(Error::Url);
+    }
+
+    let path_config = path.as_ref().join(LFS_CONFIG_FILENAME);
+    if path_config.is_file() {
+        if let Ok(url) = git2::Config::open(&path_config).and_then(|c| c.get_string("lfs.url")) {
+            return Url::parse(&url).map(Some).map_err(Error::Url);
+        }
+    }
+
+    if remote.is_local() {
+        return Ok(None);
+    }
+
+    let url = remote.url();
+
+    let path = url.path().trim_end_matches('/');
+    let path = match path.ends_with(".git") {
+        true => format!("{}/info/lfs", path),
+        false => format!("{}.git/info/lfs", path),
+    };
+
+    match url.scheme() {
+        "http" | "https" => {
+            let mut url = url.clone();
+            url.set_path(&path);
+
+            Ok(Some(url))
+        }
+        _ => {
+            let host = remote
+                .host()
+                .ok_or(Error::MissingInput(format!("repository host: {}", remote)))?;
+
+            Url::parse(&format!("https://{}{}", host, path))
+                .map(Some)
+                .map_err(Error::Url)
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                            Function: copy_local                            */
+/* -------------------------------------------------------------------------- */
+
+/// `copy_local` copies the provided objects from the LFS object directory of
+/// the local [Remote] repository (either bare or not) into the `gdpack` store.
+/// Objects which the repository doesn't have are skipped.
+fn copy_local(remote: &Remote, pointers: &[&Pointer]) -> Result<(), Error> {
+    let path_repo = remote
+        .url()
+        .to_file_path()
+        .map_err(|_| Error::MissingInput(format!("repository path: {}", remote)))?;
+
+    for pointer in pointers {
+        let path = LFS_OBJECTS_PATHS
+            .iter()
+            .map(|p| {
+                path_repo
+                    .join(p)
+                    .join(&pointer.oid[0..2])
+                    .join(&pointer.oid[2..4])
+                    .join(&pointer.oid)
+            })
+            .find(|p| p.is_file());
+
+        let Some(path) = path else {
+            println!("missing LFS object {}; skipping...", pointer.oid);
+            continue;
+        };
+
+        pointer.store(&std::fs::read(path).map_err(Error::Io)?)?;
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+/*                             Function: download                             */
+/* -------------------------------------------------------------------------- */
+
+/// `download` fetches the provided objects from the LFS server at `endpoint`
+/// using the [batch API](https://github.com/git-lfs/git-lfs/blob/main/docs/api/batch.md)
+/// and saves them into the `gdpack` store after verifying their contents.
+fn download(endpoint: &Url, pointers: &[&Pointer]) -> Result<(), Error> {
+    let client = super::get_http_client()?;
+
+    let url = Url::parse(&format!(
+        "{}/objects/batch",
+        endpoint.as_str().trim_end_matches('/')
+    ))
+    .map_err(Error::Url)?;
+
+    let request = BatchRequest {
+        operation: "download",
+        transfers: vec!["basic"],
+        objects: pointers.to_vec(),
+    };
+
+    let body = serde_json::to_vec(&request).map_err(Error::Json)?;
+
+    let bytes = super::with_retries(|| {
+        let res = auth::authorize(client.post(url.clone()), &url)?
+            .header(reqwest::header::ACCEPT, LFS_MEDIA_TYPE)
+            .header(reqwest::header::CONTENT_TYPE, LFS_MEDIA_TYPE)
+            .body(body.clone())
+            .send()
+            .map_err(Error::Request)?;
+
+        let status = res.status();
+        if status.is_client_error() || status.is_server_error() {
+            return Err(Error::Response(status));
+        }
+
+        res.bytes().map_err(Error::Request)
+    })?;
+
+    let response: BatchResponse = serde_json::from_slice(&bytes).map_err(Error::Json)?;
+
+    for pointer in pointers {
+        let object = response
+            .objects
+            .iter()
+            .find(|o| o.oid == pointer.oid)
+            .ok_or(Error::Lfs(format!("missing object: {}", pointer.oid)))?;
+
+        if let Some(e) = object.error.as_ref() {
+            return Err(Error::Lfs(format!(
+                "failed to download object {} ({}): {}",
+                pointer.oid, e.code, e.message
+            )));
+        }
+
+        let action = object
+            .actions
+            .as_ref()
+            .and_then(|a| a.download.as_ref())
+            .ok_or(Error::Lfs(format!(
+                "missing download action: {}",
+                pointer.oid
+            )))?;
+
+        let contents = super::with_retries(|| {
+            let mut req = client.get(&action.href);
+            for (key, value) in action.header.iter() {
+                req = req.header(key, value);
+            }
+
+            let res = req.send().map_err(Error::Request)?;
+
+            let status = res.status();
+            if status.is_client_error() || status.is_server_error() {
+                return Err(Error::Response(status));
+            }
+
+            res.bytes().map_err(Error::Request)
+        })?;
+
+        pointer.store(&contents)?;
+    }
+
+    Ok(())
+}
+
+/* --------------------------- Structs: Batch API --------------------------- */
+
+#[derive(Serialize)]
+struct BatchRequest<'a> {
+    operation: &'a str,
+    transfers: Vec<&'a str>,
+    objects: Vec<&'a Pointer>,
+}
+
+#[derive(Deserialize)]
+struct BatchResponse {
+    objects: Vec<BatchObject>,
+}
+
+#[derive(Deserialize)]
+struct BatchObject {
+    oid: String,
+    #[serde(default)]
+    actions: Option<BatchActions>,
+    #[serde(default)]
+    error: Option<BatchError>,
+}
+
+#[derive(Deserialize)]
+struct BatchActions {
+    download: Option<BatchAction>,
+}
+
+#[derive(Deserialize)]
+struct BatchAction {
+    href: String,
+    #[serde(default)]
+    header: HashMap<String, String>,
+}
+
+#[derive(Deserialize)]
+struct BatchError {
+    code: u16,
+    message: String,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Mod: Tests                                 */
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use url::Url;
+
+    use super::endpoint;
+    use super::Pointer;
+    use super::Remote;
+
+    /* ---------------------------- Test: Pointer --------------------------- */
+
+    #[rstest]
+    #[case(
+        "version https://git-lfs.github.com/spec/v1\noid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\nsize 12345\n",
+        Some(Pointer {
+            oid: String::from("4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393"),
+            size: 12345,
+        }),
+    )]
+    #[case(
+        "version https://git-lfs.github.com/spec/v1\noid sha256:abc\nsize 1\n",
+        None
+    )]
+    #[case("version https://git-lfs.github.com/spec/v1\nsize 1\n", None)]
+    #[case("[plugin]\nname=\"addon\"\n", None)]
+    #[case("", None)]
+    fn test_pointer_parse(#[case] input: &str, #[case] want: Option<Pointer>) {
+        // Given: The contents of a file.
+        // When: The contents are parsed as a pointer file.
+        let got = Pointer::parse(input);
+
+        // Then: The expected pointer is returned.
+        assert_eq!(got, want);
+    }
+
+    /* --------------------------- Test: endpoint --------------------------- */
+
+    #[rstest]
+    #[case(
+        "https://github.com/owner/repo",
+        "https://github.com/owner/repo.git/info/lfs"
+    )]
+    #[case(
+        "https://github.com/owner/repo.git",
+        "https://github.com/owner/repo.git/info/lfs"
+    )]
+    #[case(
+        "ssh://git@github.com/owner/repo.git",
+        "https://github.com/owner/repo.git/info/lfs"
+    )]
+    #[case(
+        "git@github.com:owner/repo.git",
+        "https://github.com/owner/repo.git/info/lfs"
+    )]
+    fn test_endpoint_from_remote(#[case] remote: &str, #[case] want: &str) {
+        // Given: A temporary directory without an '.lfsconfig' file.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A remote repository specification.
+        let remote = remote.parse::<Remote>().unwrap();
+
+        // When: The LFS endpoint is determined.
+        let got = endpoint(&remote, tmp.path());
+
+        // Then: The endpoint is derived from the repository URL.
+        assert_eq!(got.unwrap().unwrap().as_str(), want);
+    }
+
+    #[test]
+    fn test_endpoint_from_lfsconfig() {
+        // Given: A temporary directory with an '.lfsconfig' file.
+        let tmp = tempfile::tempdir().unwrap();
+        std::fs::write(
+            tmp.path().join(".lfsconfig"),
+            "[lfs]\n\turl = http://localhost:8080/lfs\n",
+        )
+        .unwrap();
+
+        // Given: A remote repository URL.
+        let remote = Remote::from(Url::parse("https://github.com/owner/repo").unwrap());
+
+        // When: The LFS endpoint is determined.
+        let got = endpoint(&remote, tmp.path());
+
+        // Then: The endpoint is read from the '.lfsconfig' file.
+        assert_eq!(got.unwrap().unwrap().as_str(), "http://localhost:8080/lfs");
+    }
+
+    #[test]
+    fn test_endpoint_from_local_remote() {
+        // Given: A temporary directory without an '.lfsconfig' file.
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A local repository URL.
+        let remote = Remote::from(Url::parse("file:///srv/git/repo.git").unwrap());
+
+        // When: The LFS endpoint is determined.
+        let got = endpoint(&remote, tmp.path());
+
+        // Then: There's no endpoint; objects are read from the repository.
+        assert_eq!(got.unwrap(), None);
+    }
+}
@@ -2,19 +2,30 @@
 //! implementation of git dependencies and provides operations for managing git-
 //! based Godot addons.
 
+/* -------------------------------- Mod: Auth ------------------------------- */
+
+mod auth;
+
 /* ------------------------------ Mod: Checkout ----------------------------- */
 
 mod checkout;
 
 pub use checkout::checkout;
+pub use checkout::include;
 pub use checkout::Checkout;
 
+use checkout::Sparse;
+
 /* ------------------------------ Mod: Database ----------------------------- */
 
 mod database;
 
 pub use database::Database;
 
+/* -------------------------------- Mod: Lfs -------------------------------- */
+
+mod lfs;
+
 /* ------------------------------ Mod: Release ------------------------------ */
 
 mod release;
@@ -29,6 +40,21 @@ pub use source::Reference;
 pub use source::Remote;
 pub use source::Source;
 
+/* ----------------------------- Mod: Transport ----------------------------- */
+
+mod transport;
+
+use transport::get_fetch_options;
+use transport::get_http_client;
+use transport::get_proxy_options;
+use transport::with_retries;
+
+/* ------------------------------- Mod: Verify ------------------------------ */
+
+mod verify;
+
+pub use verify::Verify;
+
 /* -------------------------------------------------------------------------- */
 /*                          Function: get_store_path                          */
 /* -------------------------------------------------------------------------- */
@@ -46,26 +72,107 @@ fn get_store_path() -> Result<PathBuf, Error> {
         .map_err(Error::Env)
 }
 
+/* -------------------------------------------------------------------------- */
+/*                           Function: with_mirrors                           */
+/* -------------------------------------------------------------------------- */
+
+use url::Url;
+
+/// `with_mirrors` calls `f` with each of the URLs from which `url` should be
+/// fetched (see [Settings::rewrite]), in order, until one succeeds. Transient
+/// failures are retried (see [with_retries]) before moving on to the next URL.
+/// If all of them fail, then the last error is returned.
+fn with_mirrors<T>(url: &Url, mut f: impl FnMut(&Url) -> Result<T, Error>) -> Result<T, Error> {
+    let urls = get_settings()?.rewrite(url);
+
+    let mut err = None;
+
+    for (i, url) in urls.iter().enumerate() {
+        match with_retries(|| f(url)) {
+            Ok(value) => return Ok(value),
+            Err(e) => {
+                if i + 1 < urls.len() {
+                    println!("failed to reach {}: {}; trying next mirror...", url, e);
+                }
+
+                err = Some(e);
+            }
+        }
+    }
+
+    Err(err.expect("missing url"))
+}
+
+/* -------------------------------------------------------------------------- */
+/*                           Function: get_settings                           */
+/* -------------------------------------------------------------------------- */
+
+use std::sync::OnceLock;
+
+use crate::config::settings::Settings;
+use crate::config::Configuration;
+use crate::config::Parsable;
+use crate::config::ParsableError;
+
+/// `get_settings` returns the user's [Settings], which are read from the GDPack
+/// home directory the first time they're requested. If either the home
+/// directory or the settings file doesn't exist, then default [Settings] are
+/// returned.
+pub fn get_settings() -> Result<&'static Settings, Error> {
+    static SETTINGS: OnceLock<Settings> = OnceLock::new();
+
+    if let Some(settings) = SETTINGS.get() {
+        return Ok(settings);
+    }
+
+    let settings = match get_store_path() {
+        Err(_) => Settings::default(),
+        Ok(path) => match Settings::parse_file(path.join(Settings::file_name().unwrap())) {
+            Ok(s) => s,
+            Err(ParsableError::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                Settings::default()
+            }
+            Err(e) => return Err(Error::Settings(e)),
+        },
+    };
+
+    Ok(SETTINGS.get_or_init(|| settings))
+}
+
 /* -------------------------------------------------------------------------- */
 /*                                 Enum: Error                                */
 /* -------------------------------------------------------------------------- */
 
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
+    #[error("checksum mismatch for {0}: expected sha256 '{1}' but found '{2}'")]
+    Checksum(String, String, String),
+    #[error("failed to clone repository {0}: {1}")]
+    Clone(String, git2::Error),
     #[error(transparent)]
     Env(std::env::VarError),
+    #[error("failed to fetch from repository {0}: {1}")]
+    Fetch(String, git2::Error),
     #[error(transparent)]
     Git(git2::Error),
     #[error(transparent)]
     Io(std::io::Error),
     #[error("invalid input: {0}")]
     InvalidInput(String),
+    #[error(transparent)]
+    Json(serde_json::Error),
+    #[error("git lfs: {0}")]
+    Lfs(String),
     #[error("missing input: {0}")]
     MissingInput(String),
     #[error(transparent)]
     Request(reqwest::Error),
     #[error("response failed: {0}")]
     Response(reqwest::StatusCode),
+    #[error("failed to verify signature of {0}: {1}")]
+    Signature(String, String),
+    #[error("invalid settings: {0}")]
+    Settings(ParsableError),
     #[error(transparent)]
     Url(url::ParseError),
     #[error(transparent)]
@@ -1,20 +1,53 @@
+use globset::Glob;
+use reqwest::StatusCode;
+use semver::Version;
+use semver::VersionReq;
 use serde::Deserialize;
 use serde::Serialize;
-use std::ffi::OsStr;
+use sha2::Digest;
+use sha2::Sha256;
 use std::fs::File;
 use std::io::Cursor;
+use std::path::Path;
 use std::path::PathBuf;
 use tempfile::tempdir;
 use typed_builder::TypedBuilder;
+use url::Url;
 
+use super::auth;
 use super::Database;
 use super::Error;
 use super::Remote;
+use super::Verify;
+
+const GITHUB_API_HOST: &str = "api.github.com";
+const GITHUB_API_MEDIA_TYPE: &str = "application/vnd.github+json";
+const GITHUB_HOST: &str = "github.com";
+const RELEASE_ASSET_EXTENSION: &str = "zip";
+const RELEASE_ASSET_SOURCE: &str = "source";
+const RELEASE_LATEST: &str = "latest";
+
+/// Extensions of detached signatures published alongside release assets (i.e.
+/// `minisign`, GPG or SSH, and ASCII-armored GPG signatures, respectively).
+const RELEASE_SIGNATURE_EXTENSIONS: &[&str] = &["minisig", "sig", "asc"];
+
+const STORE_CHECKSUM_EXTENSION: &str = "sha256";
+const STORE_STAGING_EXTENSION: &str = "staging";
+const STORE_VERIFIED_EXTENSION: &str = "verified";
 
 /* -------------------------------------------------------------------------- */
 /*                            Struct: GitHubRelease                           */
 /* -------------------------------------------------------------------------- */
 
+/// `GitHubRelease` specifies an asset of a release of a GitHub(-compatible)
+/// repository. The release may be an exact tag, `latest`, or a semantic version
+/// requirement (e.g. `^1.2`), the latter two of which are resolved through the
+/// host's releases API. If `asset` is omitted, then the release's only `.zip`
+/// asset is used; otherwise, it may be a name template, a glob pattern, or
+/// `source` for the host's auto-generated source archive of the tag. If
+/// `sha256` is set, then the downloaded archive must match the checksum. If
+/// `verify` is set, then the asset must have a detached signature (e.g.
+/// `asset.zip.minisig`) made by one of the trusted keys.
 #[derive(
     Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize, TypedBuilder,
 )]
@@ -23,7 +56,15 @@ pub struct GitHubRelease {
     pub repo: Remote,
     #[serde(rename = "release")]
     pub tag: String,
-    pub asset: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub asset: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub sha256: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub verify: Option<Verify>,
 }
 
 /* --------------------------- Impl: GitHubRelease -------------------------- */
@@ -31,67 +72,141 @@ pub struct GitHubRelease {
 impl GitHubRelease {
     /* --------------------------- Methods: Public -------------------------- */
 
-    /// Returns a path to the release-specific directory for the specified
-    /// [super::Remote] in the `gdpack` store.
-    pub fn get_path(&self) -> Result<PathBuf, Error> {
-        let mut path = super::get_store_path()?;
-        path.extend(&["asset", &Database::id(&self.repo)?]);
-
-        path.push(&self.tag);
+    /// Downloads and extracts the release asset into the `gdpack` store,
+    /// returning the path to the extracted contents. If the asset was already
+    /// downloaded (and matches the `sha256` checksum, if set), then it's not
+    /// downloaded again.
+    pub fn download(&self) -> Result<PathBuf, Error> {
+        self.store(&super::get_store_path()?).map(|(path, _)| path)
+    }
 
-        let mut asset = self.get_asset_name();
+    /// `get_version` returns the version of the release asset extracted at
+    /// `path` (see [GitHubRelease::download]), as determined by the tag that
+    /// the release was resolved to (e.g. from `latest` or a requirement).
+    pub fn get_version(path: impl AsRef<Path>) -> Option<Version> {
+        path.as_ref()
+            .parent()
+            .and_then(Path::file_name)
+            .and_then(|s| s.to_str())
+            .and_then(parse_tag)
+    }
 
-        let filename = PathBuf::from(&self.asset);
-        if let Some(ext) = filename.extension().and_then(OsStr::to_str) {
-            asset = asset
-                .strip_suffix(&format!(".{}", ext))
-                .unwrap_or(&asset)
-                .to_owned()
+    /// `pin` downloads the release asset and records its checksum as the
+    /// release's `sha256`, if one isn't already set. Only exact tags are
+    /// pinned because `latest` and version requirements are expected to
+    /// resolve to different assets over time.
+    pub fn pin(&mut self) -> Result<(), Error> {
+        if self.sha256.is_some() || !self.is_exact_tag() {
+            return Ok(());
         }
 
-        path.push(asset);
+        self.pin_in(&super::get_store_path()?)
+    }
 
-        Ok(path)
+    /* -------------------------- Methods: Private -------------------------- */
+
+    /// `pin_in` records the checksum of the release asset as the release's
+    /// `sha256`, storing the asset within the `gdpack` store at `root`.
+    fn pin_in(&mut self, root: &Path) -> Result<(), Error> {
+        let (_, checksum) = self.store(root)?;
+        self.sha256 = Some(checksum);
+
+        Ok(())
     }
 
-    pub fn download(&self) -> Result<(), Error> {
-        // TODO: Save compressed archives instead of extracted contents so that
-        // less space is consumed and checksum validation is easier.
-        // If the asset exists already, skip the download.
-        let path_store = self.get_path()?;
-        if path_store.as_path().is_dir()
-            && std::fs::read_dir(path_store).map_err(Error::Io)?.count() > 0
-        {
-            return Ok(());
+    /// `store` downloads and extracts the release asset into the `gdpack`
+    /// store at `root`, returning the path to the extracted contents and the
+    /// SHA-256 checksum of the downloaded archive.
+    fn store(&self, root: &Path) -> Result<(PathBuf, String), Error> {
+        let asset = self.resolve(false)?;
+
+        if asset.tag != self.tag {
+            println!(
+                "resolved release '{}' to tag '{}' for dependency: {}",
+                self.tag,
+                asset.tag,
+                self.repo.name().expect("missing remote name"),
+            );
+        }
+
+        let path_store = self.get_path(root, &asset)?;
+        let path_checksum = get_sidecar_path(&path_store, STORE_CHECKSUM_EXTENSION);
+        let path_verified = get_sidecar_path(&path_store, STORE_VERIFIED_EXTENSION);
+
+        let trusted = self.verify.as_ref().map(Verify::trusted).transpose()?;
+
+        // If the asset was completely extracted, then a checksum was recorded
+        // alongside it; skip the download if it's the expected one (and the
+        // asset's signature was verified by the same trusted keys, if needed).
+        if path_store.is_dir() {
+            if let Ok(checksum) = std::fs::read_to_string(&path_checksum) {
+                let checksum = checksum.trim().to_owned();
+                if self
+                    .sha256
+                    .as_ref()
+                    .map_or(true, |s| s.eq_ignore_ascii_case(&checksum))
+                    && trusted.as_ref().map_or(true, |v| {
+                        std::fs::read_to_string(&path_verified)
+                            .is_ok_and(|d| d.trim() == v.digest())
+                    })
+                {
+                    return Ok((path_store, checksum));
+                }
+            }
         }
 
-        let base = self.repo.assets()?;
-        let asset_url = base
-            .join(&format!("{}/{}", self.tag, self.get_asset_name()))
-            .map_err(Error::Url)?;
+        // Remove any incomplete entry, including one left behind by a download
+        // which was interrupted, so that it's restarted from scratch.
+        remove_store_entry(&path_store)?;
 
         let tmp = tempdir().map_err(Error::Io)?;
 
-        let path = tmp.path().join(self.get_asset_name());
+        let path = tmp.path().join(&asset.name);
 
         let mut file = File::create(path.as_path()).map_err(Error::Io)?;
 
-        let res = reqwest::blocking::get(asset_url.clone()).map_err(Error::Request)?;
+        let (asset, bytes) = self.download_asset(asset)?;
+
+        let checksum = format!("{:x}", Sha256::digest(&bytes));
+        if let Some(want) = self.sha256.as_ref() {
+            if !want.eq_ignore_ascii_case(&checksum) {
+                return Err(Error::Checksum(
+                    asset.url.to_string(),
+                    want.clone(),
+                    checksum,
+                ));
+            }
+        }
 
-        let status = res.status();
-        if status.is_client_error() || status.is_server_error() {
-            return Err(Error::Response(status));
+        if let Some(verify) = trusted.as_ref() {
+            self.verify_asset(&asset, &bytes, verify)?;
         }
 
-        let mut content = Cursor::new(res.bytes().map_err(Error::Request)?);
+        std::io::copy(&mut Cursor::new(bytes), &mut file).map_err(Error::Io)?;
 
-        std::io::copy(&mut content, &mut file).map_err(Error::Io)?;
+        // Extract into a staging directory next to the store entry so that a
+        // partial extraction is never mistaken for a complete one.
+        let parent = path_store
+            .parent()
+            .ok_or(Error::MissingInput(format!("store path: {:?}", path_store)))?;
+        std::fs::create_dir_all(parent).map_err(Error::Io)?;
 
-        let target = self.get_path()?;
+        let staging = tempfile::Builder::new()
+            .prefix(&get_staging_prefix(&path_store))
+            .tempdir_in(parent)
+            .map_err(Error::Io)?;
+        let target = staging.path();
 
         let mut archive =
             zip::ZipArchive::new(File::open(&path).map_err(Error::Io)?).map_err(Error::Zip)?;
 
+        // Source archives wrap their contents in a directory named after the
+        // repository and tag (e.g. 'repo-1.2.3/'), which is stripped.
+        let prefix = match asset.is_source {
+            false => None,
+            true => get_archive_root(&mut archive)?,
+        };
+
         // See https://github.com/zip-rs/zip/blob/3e88fe66c941d411cff5cf49778ba08c2ed93801/examples/extract.rs
         for i in 0..archive.len() {
             let mut file = archive.by_index(i).map_err(Error::Zip)?;
@@ -101,6 +216,13 @@ impl GitHubRelease {
                 None => continue, // Skip insecure filepaths.
             };
 
+            let filepath = match prefix.as_ref().map(|p| filepath.strip_prefix(p)) {
+                None => filepath,
+                Some(Ok(p)) if p.as_os_str().is_empty() => continue,
+                Some(Ok(p)) => p.to_owned(),
+                Some(Err(_)) => continue,
+            };
+
             let dst = target.join(filepath.as_path());
 
             if file.is_dir() {
@@ -131,18 +253,812 @@ impl GitHubRelease {
             }
         }
 
-        Ok(())
+        std::fs::rename(staging.path(), &path_store).map_err(Error::Io)?;
+        std::fs::write(&path_checksum, &checksum).map_err(Error::Io)?;
+
+        if let Some(verify) = trusted.as_ref() {
+            std::fs::write(&path_verified, verify.digest()).map_err(Error::Io)?;
+        }
+
+        Ok((path_store, checksum))
     }
 
-    /* -------------------------- Methods: Private -------------------------- */
+    /// Returns a path to the release-specific directory for the resolved
+    /// [Asset] in the `gdpack` store at `root`.
+    fn get_path(&self, root: &Path, asset: &Asset) -> Result<PathBuf, Error> {
+        let mut path = root.to_path_buf();
+        path.extend(&["asset", &Database::id(&self.repo)?]);
+
+        path.push(&asset.tag);
+
+        let name = PathBuf::from(&asset.name);
+        path.push(name.file_stem().unwrap_or(name.as_os_str()));
+
+        Ok(path)
+    }
+
+    /// `resolve` determines the tag and asset to download. The releases API is
+    /// only used if necessary, i.e. if the release isn't an exact tag, the
+    /// asset isn't an exact name, or the asset must be downloaded through the
+    /// API because the repository is private (i.e. `is_private`; see
+    /// [GitHubRelease::download_asset]).
+    fn resolve(&self, is_private: bool) -> Result<Asset, Error> {
+        let is_exact_tag = self.is_exact_tag();
+
+        if self.asset.as_deref() == Some(RELEASE_ASSET_SOURCE) {
+            let tag = match is_exact_tag {
+                true => self.tag.clone(),
+                false => self.fetch_release()?.tag_name,
+            };
+
+            return self.get_source_asset(tag, is_private);
+        }
+
+        if is_exact_tag && !is_private {
+            if let Some(name) = self
+                .asset
+                .as_deref()
+                .map(|a| self.get_asset_name(a, &self.tag))
+                .filter(|a| !is_glob(a))
+            {
+                let url = self
+                    .repo
+                    .assets()?
+                    .join(&format!("{}/{}", self.tag, name))
+                    .map_err(Error::Url)?;
+
+                let signatures = RELEASE_SIGNATURE_EXTENSIONS
+                    .iter()
+                    .map(|ext| Url::parse(&format!("{}.{}", url, ext)).map_err(Error::Url))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                return Ok(Asset {
+                    tag: self.tag.clone(),
+                    name,
+                    url,
+                    signatures,
+                    is_api: false,
+                    is_source: false,
+                });
+            }
+        }
+
+        let release = self.fetch_release()?;
+
+        let asset = self.select_asset(&release)?;
+
+        let get_url = |a: &ReleaseAsset| match is_private {
+            true => a.url.clone(),
+            false => a.browser_download_url.clone(),
+        };
+
+        let signatures = RELEASE_SIGNATURE_EXTENSIONS
+            .iter()
+            .filter_map(|ext| {
+                let name = format!("{}.{}", asset.name, ext);
+                release.assets.iter().find(|a| a.name == name)
+            })
+            .map(get_url)
+            .collect();
+
+        Ok(Asset {
+            tag: release.tag_name.clone(),
+            name: asset.name.clone(),
+            url: get_url(asset),
+            signatures,
+            is_api: is_private,
+            is_source: false,
+        })
+    }
+
+    /// `get_source_asset` returns the [Asset] for the host's auto-generated
+    /// source archive of `tag`. Source archives of private repositories can
+    /// only be downloaded through the API.
+    fn get_source_asset(&self, tag: String, is_private: bool) -> Result<Asset, Error> {
+        let namespace = self
+            .repo
+            .namespace()
+            .ok_or(Error::MissingInput("namespace".into()))?;
+        let name = self.repo.name().ok_or(Error::MissingInput("name".into()))?;
+
+        let url = match is_private {
+            true => Url::parse(&format!(
+                "{}/repos/{}/{}/zipball/{}",
+                self.get_api_url()?.as_str().trim_end_matches('/'),
+                namespace,
+                name,
+                tag
+            ))
+            .map_err(Error::Url)?,
+            false => {
+                let mut url = self.repo.url().clone();
+                url.set_path(&format!(
+                    "{}/{}/archive/refs/tags/{}.{}",
+                    namespace, name, tag, RELEASE_ASSET_EXTENSION
+                ));
+
+                url
+            }
+        };
+
+        Ok(Asset {
+            name: format!("{}.{}", RELEASE_ASSET_SOURCE, RELEASE_ASSET_EXTENSION),
+            tag,
+            url,
+            signatures: vec![],
+            is_api: false,
+            is_source: true,
+        })
+    }
+
+    /// `verify_asset` checks that one of the [Asset]'s detached signatures is
+    /// a valid signature of its contents, `data`, by one of the keys trusted by
+    /// `verify` (see [Verify::trusted]). Signatures which can't be downloaded
+    /// are skipped.
+    fn verify_asset(&self, asset: &Asset, data: &[u8], verify: &Verify) -> Result<(), Error> {
+        let mut errors = vec![];
+
+        for url in &asset.signatures {
+            let signature = match self.fetch_asset(url, asset.is_api) {
+                Ok(s) => s,
+                Err(_) => continue,
+            };
+
+            match verify.verify(data, &signature, super::verify::SSH_NAMESPACE_FILE) {
+                Ok(()) => return Ok(()),
+                Err(e) => errors.push(e),
+            }
+        }
+
+        Err(Error::Signature(
+            asset.name.clone(),
+            match errors.is_empty() {
+                true => "no signature found".to_owned(),
+                false => errors.join("; "),
+            },
+        ))
+    }
+
+    /// `download_asset` downloads the contents of the resolved `asset`, returning
+    /// the [Asset] which was actually downloaded. Assets of private repositories
+    /// can only be downloaded through the API, so if the public download is
+    /// rejected and an access token is available for the host, then the asset
+    /// is resolved again and downloaded through the API. Public assets thus
+    /// don't count against the API's rate limits.
+    fn download_asset(&self, asset: Asset) -> Result<(Asset, Vec<u8>), Error> {
+        let err = match self.fetch_asset(&asset.url, asset.is_api) {
+            Ok(bytes) => return Ok((asset, bytes)),
+            Err(e) => e,
+        };
+
+        let is_denied = matches!(
+            err,
+            Error::Response(
+                StatusCode::NOT_FOUND | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN
+            )
+        );
+
+        let host = self.repo.host().unwrap_or_default();
+        if asset.is_api || !is_denied || auth::token(&host)?.is_none() {
+            return Err(err);
+        }
+
+        let asset = self.resolve(true)?;
+        let bytes = self.fetch_asset(&asset.url, asset.is_api)?;
+
+        Ok((asset, bytes))
+    }
+
+    /// `fetch_asset` downloads the contents of a release asset at `url` (or one
+    /// of its mirrors). If `is_api`, then `url` is a releases API URL, which is
+    /// how assets of private repositories are downloaded. The whole body is read
+    /// before returning so that an interrupted download is retried from the
+    /// start.
+    fn fetch_asset(&self, url: &Url, is_api: bool) -> Result<Vec<u8>, Error> {
+        let client = super::get_http_client()?;
+
+        super::with_mirrors(url, |url| {
+            let mut req = auth::authorize(client.get(url.clone()), url)?;
+            if is_api {
+                req = req.header(reqwest::header::ACCEPT, "application/octet-stream");
+            }
+
+            let res = req.send().map_err(Error::Request)?;
+
+            let status = res.status();
+            if status.is_client_error() || status.is_server_error() {
+                return Err(Error::Response(status));
+            }
+
+            res.bytes().map(|b| b.to_vec()).map_err(Error::Request)
+        })
+    }
+
+    /// `fetch_release` looks up the release through the host's releases API (or
+    /// one of its mirrors). When matching a version requirement, every page of
+    /// the repository's releases is considered.
+    fn fetch_release(&self) -> Result<ReleaseResponse, Error> {
+        let base = format!(
+            "{}/repos/{}/{}/releases",
+            self.get_api_url()?.as_str().trim_end_matches('/'),
+            self.repo
+                .namespace()
+                .ok_or(Error::MissingInput("namespace".into()))?,
+            self.repo.name().ok_or(Error::MissingInput("name".into()))?,
+        );
+
+        let req = self.get_version_req();
+
+        let url = match (self.tag.as_str(), req.as_ref()) {
+            (_, Some(_)) => format!("{}?per_page=100", base),
+            (RELEASE_LATEST, None) => format!("{}/latest", base),
+            (tag, None) => format!("{}/tags/{}", base, tag),
+        };
+
+        let mut url = Url::parse(&url).map_err(Error::Url)?;
+
+        let req = match req {
+            None => {
+                let (bytes, _) = self.fetch_api(&url)?;
+                return serde_json::from_slice(&bytes).map_err(Error::Json);
+            }
+            Some(req) => req,
+        };
+
+        let mut releases = vec![];
+
+        // Collect every page of releases, following the `next` links returned
+        // by the API; releases are listed by creation date rather than version.
+        loop {
+            let (bytes, next) = self.fetch_api(&url)?;
+
+            releases.extend(
+                serde_json::from_slice::<Vec<ReleaseResponse>>(&bytes).map_err(Error::Json)?,
+            );
+
+            match next {
+                None => break,
+                Some(next) => url = next,
+            }
+        }
+
+        // Select the greatest version satisfying the requirement.
+        releases
+            .into_iter()
+            .filter(|r| !r.draft)
+            .filter_map(|r| parse_tag(&r.tag_name).map(|v| (v, r)))
+            .filter(|(v, _)| req.matches(v))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, r)| r)
+            .ok_or(Error::MissingInput(format!(
+                "release matching '{}': {}",
+                self.tag, self.repo
+            )))
+    }
+
+    /// `fetch_api` requests `url` from the host's releases API (or one of its
+    /// mirrors), returning the response body and the URL of the next page of
+    /// results, if any.
+    fn fetch_api(&self, url: &Url) -> Result<(Vec<u8>, Option<Url>), Error> {
+        let client = super::get_http_client()?;
+
+        super::with_mirrors(url, |url| {
+            let res = auth::authorize(client.get(url.clone()), url)?
+                .header(reqwest::header::ACCEPT, GITHUB_API_MEDIA_TYPE)
+                .send()
+                .map_err(Error::Request)?;
+
+            let status = res.status();
+            if status.is_client_error() || status.is_server_error() {
+                return Err(Error::Response(status));
+            }
+
+            let next = res
+                .headers()
+                .get(reqwest::header::LINK)
+                .and_then(|v| v.to_str().ok())
+                .and_then(parse_next_link);
+
+            let bytes = res.bytes().map_err(Error::Request)?;
+
+            Ok((bytes.to_vec(), next))
+        })
+    }
+
+    /// `select_asset` selects the configured asset from the `release`. If no
+    /// asset is configured, then the release must have exactly one `.zip`
+    /// asset.
+    fn select_asset<'a>(&self, release: &'a ReleaseResponse) -> Result<&'a ReleaseAsset, Error> {
+        let matches = match self.asset.as_deref() {
+            None => release
+                .assets
+                .iter()
+                .filter(|a| {
+                    PathBuf::from(&a.name)
+                        .extension()
+                        .is_some_and(|e| e == RELEASE_ASSET_EXTENSION)
+                })
+                .collect::<Vec<_>>(),
+            Some(asset) => {
+                let pattern = self.get_asset_name(asset, &release.tag_name);
+
+                let glob = Glob::new(&pattern)
+                    .map_err(|e| Error::InvalidInput(format!("asset: {}", e)))?
+                    .compile_matcher();
+
+                release
+                    .assets
+                    .iter()
+                    .filter(|a| glob.is_match(&a.name))
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        match matches.as_slice() {
+            [asset] => Ok(asset),
+            [] => Err(Error::MissingInput(format!(
+                "release asset ({}): {}",
+                release.tag_name,
+                self.asset.as_deref().unwrap_or("*.zip")
+            ))),
+            _ => Err(Error::InvalidInput(format!(
+                "multiple release assets match; specify one of: {}",
+                matches
+                    .iter()
+                    .map(|a| a.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        }
+    }
+
+    /// `get_api_url` returns the base URL of the host's releases API. This is
+    /// either the host's `api-url` setting or, by default, the GitHub API (for
+    /// `github.com`) or the GitHub Enterprise API (for all other hosts).
+    fn get_api_url(&self) -> Result<Url, Error> {
+        let host = self.repo.host().ok_or(Error::MissingInput(format!(
+            "repository host: {}",
+            self.repo
+        )))?;
+
+        if let Some(url) = super::get_settings()?
+            .host(&host)
+            .and_then(|s| s.api_url.as_ref())
+        {
+            return Ok(url.clone());
+        }
+
+        let url = match host.as_str() {
+            GITHUB_HOST => format!("https://{}", GITHUB_API_HOST),
+            host => format!("https://{}/api/v3", host),
+        };
+
+        Url::parse(&url).map_err(Error::Url)
+    }
 
     /// `get_asset_name` returns the name of the release asset after hydrating
-    /// template variables.
-    fn get_asset_name(&self) -> String {
-        self.asset
-            .replace("{tag}", &self.tag)
+    /// template variables for the provided `tag`.
+    fn get_asset_name(&self, asset: &str, tag: &str) -> String {
+        asset
+            .replace("{tag}", tag)
             .replace("{name}", self.repo.name().as_deref().unwrap_or("{name}"))
             .replace("{repo}", self.repo.name().as_deref().unwrap_or("{repo}"))
-            .replace("{release}", &self.tag)
+            .replace("{release}", tag)
+    }
+
+    /// `is_exact_tag` returns whether the release is an exact tag (i.e. not
+    /// `latest` or a semantic version requirement).
+    fn is_exact_tag(&self) -> bool {
+        self.tag != RELEASE_LATEST && self.get_version_req().is_none()
+    }
+
+    /// `get_version_req` returns the semantic version requirement specified by
+    /// the release, if any. To avoid confusion with exact tags, requirements
+    /// must begin with an operator (e.g. `^1.2` or `>=1.0, <2.0`).
+    fn get_version_req(&self) -> Option<VersionReq> {
+        self.tag
+            .trim()
+            .starts_with(['^', '~', '=', '<', '>', '*'])
+            .then(|| VersionReq::parse(&self.tag).ok())
+            .flatten()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                Struct: Asset                               */
+/* -------------------------------------------------------------------------- */
+
+/// `Asset` is a resolved release asset, ready to be downloaded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct Asset {
+    tag: String,
+    name: String,
+    url: Url,
+    /// URLs of the asset's possible detached signatures.
+    signatures: Vec<Url>,
+    /// Whether `url` is a releases API URL (rather than a direct download).
+    is_api: bool,
+    /// Whether this is the host's auto-generated source archive.
+    is_source: bool,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                            Structs: Releases API                           */
+/* -------------------------------------------------------------------------- */
+
+/// The subset of a GitHub [release](https://docs.github.com/en/rest/releases/releases#get-a-release-by-tag-name)
+/// which is needed to download its assets.
+#[derive(Debug, Deserialize)]
+struct ReleaseResponse {
+    tag_name: String,
+    #[serde(default)]
+    draft: bool,
+    assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseAsset {
+    name: String,
+    url: Url,
+    browser_download_url: Url,
+}
+
+/* -------------------------------------------------------------------------- */
+/*                             Function: parse_tag                            */
+/* -------------------------------------------------------------------------- */
+
+/// `parse_tag` parses a release tag (optionally prefixed with `v`) into a
+/// [Version].
+fn parse_tag(tag: &str) -> Option<Version> {
+    Version::parse(tag.strip_prefix('v').unwrap_or(tag)).ok()
+}
+
+/* -------------------------------------------------------------------------- */
+/*                          Function: parse_next_link                         */
+/* -------------------------------------------------------------------------- */
+
+/// `parse_next_link` parses the URL of the next page of results from the value
+/// of an HTTP `Link` header (e.g. `<https://...?page=2>; rel="next"`).
+fn parse_next_link(value: &str) -> Option<Url> {
+    value.split(',').find_map(|link| {
+        let mut parts = link.split(';').map(str::trim);
+
+        let url = parts.next()?.strip_prefix('<')?.strip_suffix('>')?;
+
+        parts
+            .any(|p| p == "rel=\"next\"" || p == "rel=next")
+            .then(|| Url::parse(url).ok())
+            .flatten()
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+/*                         Function: get_archive_root                         */
+/* -------------------------------------------------------------------------- */
+
+/// `get_archive_root` returns the single top-level directory of the `archive`,
+/// if all of its entries are contained within one.
+fn get_archive_root(archive: &mut zip::ZipArchive<File>) -> Result<Option<PathBuf>, Error> {
+    let mut root: Option<PathBuf> = None;
+
+    for i in 0..archive.len() {
+        let file = archive.by_index(i).map_err(Error::Zip)?;
+
+        let path = match file.enclosed_name() {
+            None => continue,
+            Some(p) => p.to_owned(),
+        };
+
+        let first = match path.components().next() {
+            None => continue,
+            Some(c) => PathBuf::from(c.as_os_str()),
+        };
+
+        // A file at the top-level means there's no wrapper directory.
+        if !file.is_dir() && path == first {
+            return Ok(None);
+        }
+
+        match root.as_ref() {
+            None => root = Some(first),
+            Some(r) if *r == first => {}
+            Some(_) => return Ok(None),
+        }
+    }
+
+    Ok(root)
+}
+
+/* -------------------------------------------------------------------------- */
+/*                         Function: get_sidecar_path                         */
+/* -------------------------------------------------------------------------- */
+
+/// `get_sidecar_path` returns the path to a file with extension `ext` which
+/// records metadata about the store entry at `path` (e.g. the checksum of the
+/// extracted archive). These files are written only after extraction completes.
+fn get_sidecar_path(path: &Path, ext: &str) -> PathBuf {
+    let mut path = path.as_os_str().to_owned();
+    path.push(".");
+    path.push(ext);
+
+    PathBuf::from(path)
+}
+
+/* -------------------------------------------------------------------------- */
+/*                        Function: get_staging_prefix                        */
+/* -------------------------------------------------------------------------- */
+
+/// `get_staging_prefix` returns the file name prefix of the temporary
+/// directories into which the store entry at `path` is extracted.
+fn get_staging_prefix(path: &Path) -> String {
+    let path = get_sidecar_path(path, STORE_STAGING_EXTENSION);
+
+    format!(
+        "{}-",
+        path.file_name().unwrap_or_default().to_string_lossy()
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+/*                        Function: remove_store_entry                        */
+/* -------------------------------------------------------------------------- */
+
+/// `remove_store_entry` deletes the extracted store entry at `path` along with
+/// its sidecar files and any staging directories left behind by an extraction
+/// which was interrupted.
+fn remove_store_entry(path: &Path) -> Result<(), Error> {
+    for ext in [STORE_CHECKSUM_EXTENSION, STORE_VERIFIED_EXTENSION] {
+        let path_sidecar = get_sidecar_path(path, ext);
+        if path_sidecar.exists() {
+            std::fs::remove_file(path_sidecar).map_err(Error::Io)?;
+        }
+    }
+
+    if path.exists() {
+        std::fs::remove_dir_all(path).map_err(Error::Io)?;
+    }
+
+    let prefix = get_staging_prefix(path);
+
+    let entries = match path.parent().map(std::fs::read_dir) {
+        Some(Ok(entries)) => entries,
+        _ => return Ok(()),
+    };
+
+    for entry in entries {
+        let entry = entry.map_err(Error::Io)?;
+        if entry.file_name().to_string_lossy().starts_with(&prefix) {
+            std::fs::remove_dir_all(entry.path()).map_err(Error::Io)?;
+        }
+    }
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+/*                              Function: is_glob                             */
+/* -------------------------------------------------------------------------- */
+
+/// `is_glob` returns whether the asset name `s` is a glob pattern.
+fn is_glob(s: &str) -> bool {
+    s.contains(['*', '?', '[', '{'])
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Mod: Tests                                 */
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::get_archive_root;
+    use super::get_sidecar_path;
+    use super::parse_next_link;
+    use super::remove_store_entry;
+    use super::Asset;
+    use super::GitHubRelease;
+    use super::ReleaseResponse;
+    use super::STORE_CHECKSUM_EXTENSION;
+
+    /* ------------------ Test: GitHubRelease::select_asset ----------------- */
+
+    fn release() -> ReleaseResponse {
+        serde_json::from_str(
+            r#"{
+  "tag_name": "v1.2.3",
+  "assets": [
+    { "name": "addon-v1.2.3.zip", "url": "https://api.github.com/a/1", "browser_download_url": "https://github.com/d/1" },
+    { "name": "addon-v1.2.3.tar.gz", "url": "https://api.github.com/a/2", "browser_download_url": "https://github.com/d/2" }
+  ]
+}"#,
+        )
+        .unwrap()
+    }
+
+    #[rstest]
+    #[case(None, Some("addon-v1.2.3.zip"))]
+    #[case(Some("*.zip"), Some("addon-v1.2.3.zip"))]
+    #[case(Some("{name}-{tag}.tar.gz"), Some("addon-v1.2.3.tar.gz"))]
+    #[case(Some("addon-*"), None)]
+    #[case(Some("missing.zip"), None)]
+    fn test_github_release_select_asset(#[case] asset: Option<&str>, #[case] want: Option<&str>) {
+        // Given: A release specification with an optional asset pattern.
+        let release = GitHubRelease::builder()
+            .repo("https://github.com/owner/addon".parse().unwrap())
+            .tag("latest".to_owned())
+            .asset(asset.map(str::to_owned))
+            .build();
+
+        // When: An asset is selected from a release.
+        let response = self::release();
+        let got = release.select_asset(&response);
+
+        // Then: The expected asset is selected (or none are).
+        assert_eq!(got.ok().map(|a| a.name.as_str()), want);
+    }
+
+    /* ------------------------ Test: parse_next_link ----------------------- */
+
+    #[rstest]
+    #[case("", None)]
+    #[case(r#"<https://api.github.com/r?page=1>; rel="prev""#, None)]
+    #[case(
+        r#"<https://api.github.com/r?page=2>; rel="next""#,
+        Some("https://api.github.com/r?page=2")
+    )]
+    #[case(
+        r#"<https://api.github.com/r?page=1>; rel="prev", <https://api.github.com/r?page=3>; rel="next""#,
+        Some("https://api.github.com/r?page=3")
+    )]
+    fn test_parse_next_link(#[case] value: &str, #[case] want: Option<&str>) {
+        // Given: The value of a 'Link' header.
+        // When: The URL of the next page is parsed.
+        let got = parse_next_link(value);
+
+        // Then: The 'next' link is returned, if present.
+        assert_eq!(got.as_ref().map(url::Url::as_str), want);
+    }
+
+    /* ----------------------- Test: get_archive_root ----------------------- */
+
+    #[rstest]
+    #[case(&["repo-1.0.0/", "repo-1.0.0/addons/a/plugin.cfg"], Some("repo-1.0.0"))]
+    #[case(&["repo-1.0.0/addons/a/plugin.cfg", "README.md"], None)]
+    #[case(&["a/plugin.cfg", "b/plugin.cfg"], None)]
+    fn test_get_archive_root(#[case] entries: &[&str], #[case] want: Option<&str>) {
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A zip archive with the provided entries.
+        let path = tmp.path().join("archive.zip");
+
+        let opts = zip::write::SimpleFileOptions::default();
+
+        let mut writer = zip::ZipWriter::new(std::fs::File::create(&path).unwrap());
+        for entry in entries {
+            match entry.ends_with('/') {
+                true => writer.add_directory(*entry, opts).unwrap(),
+                false => writer.start_file(*entry, opts).unwrap(),
+            }
+        }
+        writer.finish().unwrap();
+
+        // When: The archive's root directory is determined.
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&path).unwrap()).unwrap();
+        let got = get_archive_root(&mut archive).unwrap();
+
+        // Then: Only a single wrapper directory is returned.
+        assert_eq!(got, want.map(std::path::PathBuf::from));
+    }
+
+    /* ---------------------- Test: remove_store_entry ---------------------- */
+
+    #[test]
+    fn test_remove_store_entry_removes_staging_dirs() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A store entry with a sidecar file, a staging directory left
+        // behind by an interrupted extraction, and another asset's entry.
+        let path = tmp.path().join("addon");
+        std::fs::create_dir_all(path.join("addons")).unwrap();
+        std::fs::write(tmp.path().join("addon.sha256"), "abc").unwrap();
+        std::fs::create_dir_all(tmp.path().join("addon.staging-Xy12/addons")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("addon-extra")).unwrap();
+
+        // When: The store entry is removed.
+        remove_store_entry(&path).unwrap();
+
+        // Then: Only the other asset's entry remains.
+        let got = std::fs::read_dir(tmp.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name())
+            .collect::<Vec<_>>();
+
+        assert_eq!(got, vec![std::ffi::OsString::from("addon-extra")]);
+    }
+
+    /* --------------------- Test: GitHubRelease::pin_in -------------------- */
+
+    #[test]
+    fn test_github_release_pin_in_records_stored_checksum() {
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A release of an exact tag and asset.
+        let mut release = GitHubRelease::builder()
+            .repo("https://example.com/owner/addon".parse().unwrap())
+            .tag("v1.0.0".to_owned())
+            .asset(Some("addon.zip".to_owned()))
+            .build();
+
+        // Given: A store which already contains the extracted asset.
+        let asset = release.resolve(false).unwrap();
+        let path = release.get_path(tmp.path(), &asset).unwrap();
+
+        std::fs::create_dir_all(&path).unwrap();
+        std::fs::write(get_sidecar_path(&path, STORE_CHECKSUM_EXTENSION), "abc123").unwrap();
+
+        // When: The release is pinned.
+        release.pin_in(tmp.path()).unwrap();
+
+        // Then: The stored asset's checksum is recorded.
+        assert_eq!(release.sha256.as_deref(), Some("abc123"));
+    }
+
+    /* ------------------ Test: GitHubRelease::get_version ------------------ */
+
+    #[rstest]
+    #[case("latest")]
+    #[case("^1.2")]
+    #[case("v1.2.3")]
+    fn test_github_release_get_version_uses_resolved_tag(#[case] tag: &str) {
+        let tmp = tempfile::tempdir().unwrap();
+
+        // Given: A release which was resolved to an exact tag.
+        let release = GitHubRelease::builder()
+            .repo("https://example.com/owner/addon".parse().unwrap())
+            .tag(tag.to_owned())
+            .asset(Some("addon.zip".to_owned()))
+            .build();
+
+        let asset = Asset {
+            tag: "v1.2.3".to_owned(),
+            name: "addon.zip".to_owned(),
+            url: "https://example.com/addon.zip".parse().unwrap(),
+            signatures: vec![],
+            is_api: false,
+            is_source: false,
+        };
+
+        // When: The version of the stored asset is determined.
+        let path = release.get_path(tmp.path(), &asset).unwrap();
+        let got = GitHubRelease::get_version(path);
+
+        // Then: The version is parsed from the resolved tag.
+        assert_eq!(got, Some(semver::Version::new(1, 2, 3)));
+    }
+
+    /* ---------------- Test: GitHubRelease::get_version_req ---------------- */
+
+    #[rstest]
+    #[case("v1.2.3", None)]
+    #[case("1.2.3", None)]
+    #[case("latest", None)]
+    #[case("^1.2", Some("^1.2"))]
+    #[case(">=1.0, <2.0", Some(">=1.0, <2.0"))]
+    fn test_github_release_get_version_req(#[case] tag: &str, #[case] want: Option<&str>) {
+        // Given: A release specification.
+        let release = GitHubRelease::builder()
+            .repo("https://github.com/owner/addon".parse().unwrap())
+            .tag(tag.to_owned())
+            .build();
+
+        // When: The release's version requirement is parsed.
+        let got = release.get_version_req();
+
+        // Then: Only requirements starting with an operator are parsed.
+        assert_eq!(
+            got.map(|r| r.to_string()),
+            want.map(|r| r.parse::<semver::VersionReq>().unwrap().to_string())
+        );
     }
 }
@@ -9,9 +9,12 @@ use url::Url;
 /* -------------------------------------------------------------------------- */
 
 /// Remote is a newtype wrapper around [Url] which adds helpful methods for
-/// extracting parts of a remotely-hosted git repository.
+/// extracting parts of a remotely-hosted git repository. SCP-style remotes
+/// (e.g. `git@github.com:owner/repo.git`) are normalized into `ssh://` URLs,
+/// but their original spelling is kept so that they're serialized as written.
 #[derive(Clone, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
-pub struct Remote(Url);
+#[serde(try_from = "String", into = "String")]
+pub struct Remote(Url, Option<String>);
 
 /* ------------------------------ Impl: Remote ------------------------------ */
 
@@ -21,40 +24,65 @@ impl Remote {
         self.0.host().as_ref().map(Host::<&str>::to_string)
     }
 
-    /// Extracts and returns the name of the remote repository.
+    /// Returns whether the remote repository is on the local filesystem (i.e.
+    /// has a `file://` URL).
+    pub fn is_local(&self) -> bool {
+        self.0.scheme() == "file"
+    }
+
+    /// Extracts and returns the name of the remote repository (i.e. the final
+    /// segment of its path).
     pub fn name(&self) -> Option<String> {
         self.0
             .path()
             .trim_matches('/')
-            .split('/')
-            .nth(1)
-            .and_then(|s| s.strip_suffix(".git").or(Some(s)))
+            .rsplit('/')
+            .next()
+            .filter(|s| !s.is_empty())
+            .map(|s| s.strip_suffix(".git").unwrap_or(s))
             .map(str::to_owned)
     }
 
-    /// Extracts and returns the owner of the remote repository.
-    pub fn owner(&self) -> Option<String> {
+    /// Extracts and returns the namespace of the remote repository, which is
+    /// every segment of its path but the last. This is the owner on most hosts,
+    /// but may be nested (e.g. GitLab subgroups like `studio/tools`).
+    pub fn namespace(&self) -> Option<String> {
         self.0
             .path()
             .trim_matches('/')
-            .split('/')
-            .next()
+            .rsplit_once('/')
+            .map(|(namespace, _)| namespace)
+            .filter(|s| !s.is_empty())
             .map(str::to_owned)
     }
 
+    /// Resolves the provided `url` against the remote repository. Relative
+    /// URLs (i.e. those starting with `./` or `../`, as are commonly used for
+    /// git submodules) are resolved relative to the repository itself.
+    pub fn join(&self, url: &str) -> Result<Remote, super::Error> {
+        if !(url.starts_with("./") || url.starts_with("../")) {
+            return url.parse();
+        }
+
+        let mut base = self.0.clone();
+        base.set_path(&format!("{}/", base.path().trim_end_matches('/')));
+
+        base.join(url).map(Remote::from).map_err(super::Error::Url)
+    }
+
     /// Returns a reference to the underlying [Url].
     pub fn url(&self) -> &Url {
         &self.0
     }
 
-    /// Returns a reference to the underlying [Url].
+    /// Returns the base [Url] of the remote repository's release assets.
     pub fn assets(&self) -> Result<Url, super::Error> {
         let mut assets_url = self.0.clone();
 
         assets_url.set_path(&format!(
             "{}/{}/releases/download/",
-            self.owner()
-                .ok_or(super::Error::MissingInput("owner".into()))?,
+            self.namespace()
+                .ok_or(super::Error::MissingInput("namespace".into()))?,
             self.name()
                 .ok_or(super::Error::MissingInput("name".into()))?,
         ));
@@ -67,7 +95,10 @@ impl Remote {
 
 impl std::fmt::Display for Remote {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str(self.0.as_str().trim_end_matches('/'))
+        match self.1.as_ref() {
+            Some(s) => f.write_str(s),
+            None => f.write_str(self.0.as_str().trim_end_matches('/')),
+        }
     }
 }
 
@@ -75,7 +106,72 @@ impl std::fmt::Display for Remote {
 
 impl From<Url> for Remote {
     fn from(value: Url) -> Self {
-        Remote(value)
+        Remote(value, None)
+    }
+}
+
+/* ------------------------------ Impl: FromStr ----------------------------- */
+
+impl std::str::FromStr for Remote {
+    type Err = super::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        // Convert an SCP-style remote (i.e. '[user@]host:path') into the
+        // equivalent 'ssh://' URL so that it can be handled like any other.
+        if is_scp_like(s) {
+            let (authority, path) = s.split_once(':').expect("missing separator");
+
+            return Url::parse(&format!(
+                "ssh://{}/{}",
+                authority,
+                path.trim_start_matches('/')
+            ))
+            .map(|url| Remote(url, Some(s.to_owned())))
+            .map_err(super::Error::Url);
+        }
+
+        Url::parse(s).map(Remote::from).map_err(super::Error::Url)
+    }
+}
+
+/* -------------------------- Impl: TryFrom<String> ------------------------- */
+
+impl TryFrom<String> for Remote {
+    type Error = super::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+/* --------------------------- Impl: From<Remote> --------------------------- */
+
+impl From<Remote> for String {
+    fn from(value: Remote) -> Self {
+        value.1.unwrap_or_else(|| value.0.into())
+    }
+}
+
+/* -------------------------- Function: is_scp_like ------------------------- */
+
+/// Returns whether `s` is an SCP-style git remote (i.e. `[user@]host:path`).
+/// This mirrors `git`'s own detection: the first `:` must precede any `/` and
+/// the string can't contain `://`. Single letter hosts are rejected so that
+/// Windows paths (e.g. `C:/addons`) aren't mistaken for remotes.
+fn is_scp_like(s: &str) -> bool {
+    if s.contains("://") {
+        return false;
+    }
+
+    match s.split_once(':') {
+        None => false,
+        Some((authority, path)) => {
+            let host = authority.rsplit('@').next().unwrap_or(authority);
+
+            host.len() > 1 && !authority.contains(['/', '\\']) && !path.is_empty()
+        }
     }
 }
 
@@ -93,6 +189,16 @@ pub struct Source {
     pub reference: Option<Reference>,
     #[serde(rename = "git")]
     pub repo: Remote,
+    /// Whether to recursively initialize the repository's submodules. If not
+    /// set, submodules are initialized whenever a `.gitmodules` file exists.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub submodules: Option<bool>,
+    /// The keys trusted to sign the checked out tag (or commit). If set, then
+    /// the revision's signature must verify.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub verify: Option<super::Verify>,
 }
 
 /* -------------------------------------------------------------------------- */
@@ -141,6 +247,49 @@ impl Reference {
             },
         }
     }
+
+    /// Resolves the revision `name` into a [Reference] by listing the refs of
+    /// the [Remote] repository; tags are preferred over branches. If `name`
+    /// isn't a tag or branch, then it's assumed to be a [Reference::Rev].
+    pub fn resolve(remote: &Remote, name: &str) -> Result<Reference, super::Error> {
+        if is_commit_hash_like(name) {
+            return Ok(Reference::Rev(name.to_owned()));
+        }
+
+        let refs = super::with_mirrors(remote.url(), |url| {
+            let remote = Remote::from(url.clone());
+
+            let mut remote_git = git2::Remote::create_detached(remote.to_string().as_str())
+                .map_err(super::Error::Git)?;
+
+            remote_git
+                .connect_auth(
+                    git2::Direction::Fetch,
+                    Some(super::auth::callbacks(&remote)?),
+                    Some(super::get_proxy_options()?),
+                )
+                .map_err(|e| super::Error::Fetch(remote.to_string(), e))?;
+
+            let refs = remote_git
+                .list()
+                .map_err(super::Error::Git)?
+                .iter()
+                .map(|h| h.name().to_owned())
+                .collect::<Vec<_>>();
+
+            Ok(refs)
+        })?;
+
+        if refs.contains(&format!("refs/tags/{}", name)) {
+            return Ok(Reference::Tag(name.to_owned()));
+        }
+
+        if refs.contains(&format!("refs/heads/{}", name)) {
+            return Ok(Reference::Branch(name.to_owned()));
+        }
+
+        Ok(Reference::Rev(name.to_owned()))
+    }
 }
 
 /* ------------------------------ Impl: Display ----------------------------- */
@@ -160,3 +309,91 @@ impl std::fmt::Display for Reference {
 fn is_commit_hash_like(id: &str) -> bool {
     id.len() >= 7 && id.chars().all(|ch| ch.is_ascii_hexdigit())
 }
+
+/* -------------------------------------------------------------------------- */
+/*                                 Mod: Tests                                 */
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+
+    use super::Remote;
+
+    /* ---------------------------- Test: Remote ---------------------------- */
+
+    #[rstest]
+    #[case(
+        "https://github.com/owner/repo.git",
+        "https://github.com/owner/repo.git"
+    )]
+    #[case("git@github.com:owner/repo.git", "ssh://git@github.com/owner/repo.git")]
+    #[case("github.com:owner/repo", "ssh://github.com/owner/repo")]
+    #[case("git@host:repo.git", "ssh://git@host/repo.git")]
+    #[case("git@host:~user/repo.git", "ssh://git@host/~user/repo.git")]
+    #[case("git@host:/srv/repo.git", "ssh://git@host/srv/repo.git")]
+    #[case(
+        "ssh://git@github.com:22/owner/repo.git",
+        "ssh://git@github.com:22/owner/repo.git"
+    )]
+    fn test_remote_from_str(#[case] input: &str, #[case] want: &str) {
+        // Given: A git remote specification.
+        // When: The remote is parsed.
+        let got = input.parse::<Remote>().unwrap();
+
+        // Then: The remote is normalized into the expected URL.
+        assert_eq!(got.url().as_str(), want);
+    }
+
+    #[rstest]
+    #[case("https://github.com/owner/repo.git")]
+    #[case("git@github.com:owner/repo.git")]
+    #[case("git@host:/srv/repo.git")]
+    fn test_remote_serializes_as_written(#[case] input: &str) {
+        // Given: A parsed git remote specification.
+        let remote = input.parse::<Remote>().unwrap();
+
+        // When: The remote is converted back into a string.
+        let got = String::from(remote.clone());
+
+        // Then: The original specification is preserved.
+        assert_eq!(got, input);
+        assert_eq!(remote.to_string(), input.trim_end_matches('/'));
+    }
+
+    #[rstest]
+    #[case("https://github.com/owner/repo.git", Some("owner"), Some("repo"))]
+    #[case(
+        "https://gitlab.com/studio/tools/godot-ui.git",
+        Some("studio/tools"),
+        Some("godot-ui")
+    )]
+    #[case("git@gitlab.com:a/b/c/d", Some("a/b/c"), Some("d"))]
+    #[case("git@host:repo.git", None, Some("repo"))]
+    #[case("https://github.com/repo", None, Some("repo"))]
+    fn test_remote_namespace_and_name(
+        #[case] input: &str,
+        #[case] namespace: Option<&str>,
+        #[case] name: Option<&str>,
+    ) {
+        // Given: A git remote with a (possibly nested) namespace.
+        let remote = input.parse::<Remote>().unwrap();
+
+        // When: The namespace and name are extracted.
+        // Then: The name is the final path segment.
+        assert_eq!(remote.namespace().as_deref(), namespace);
+        assert_eq!(remote.name().as_deref(), name);
+    }
+
+    #[rstest]
+    #[case("./addons:x")]
+    #[case("addons")]
+    fn test_remote_from_str_rejects_paths(#[case] input: &str) {
+        // Given: A filesystem path.
+        // When: The path is parsed as a remote.
+        let got = input.parse::<Remote>();
+
+        // Then: The path is rejected.
+        assert!(got.is_err());
+    }
+}
@@ -0,0 +1,340 @@
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use super::auth;
+use super::Error;
+use super::Remote;
+
+const HTTP_USER_AGENT: &str = concat!("gdpack/", env!("CARGO_PKG_VERSION"));
+
+const RETRY_BACKOFF_INITIAL: Duration = Duration::from_millis(500);
+const RETRY_BACKOFF_MAX: Duration = Duration::from_secs(30);
+const RETRY_DEFAULT_COUNT: u32 = 3;
+
+/// Substrings of `libgit2` error messages which indicate that the connection
+/// failed or was interrupted, rather than that the request was invalid.
+const GIT_TRANSIENT_MESSAGES: &[&str] = &[
+    "broken pipe",
+    "connection reset",
+    "early eof",
+    "failed to connect",
+    "socket",
+    "timed out",
+];
+const GIT_HTTP_STATUS_PREFIX: &str = "unexpected http status code: ";
+const GIT_CONNECT_PREFIX: &str = "failed to connect";
+
+/* -------------------------------------------------------------------------- */
+/*                          Function: get_http_client                         */
+/* -------------------------------------------------------------------------- */
+
+/// `get_http_client` returns a new HTTP client for downloading assets, which is
+/// configured using the user's network settings. Note that some hosts (e.g. the
+/// GitHub API) reject requests without a user agent.
+pub(super) fn get_http_client() -> Result<reqwest::blocking::Client, Error> {
+    let settings = &super::get_settings()?.network;
+
+    let mut builder = reqwest::blocking::Client::builder()
+        .user_agent(settings.user_agent.as_deref().unwrap_or(HTTP_USER_AGENT));
+
+    if let Some(proxy) = settings.proxy.as_ref() {
+        builder = builder.proxy(reqwest::Proxy::all(proxy.clone()).map_err(Error::Request)?);
+    }
+
+    for path in &settings.ca_bundles {
+        let contents = std::fs::read(auth::expand_home(path.clone())).map_err(Error::Io)?;
+
+        for cert in reqwest::Certificate::from_pem_bundle(&contents).map_err(Error::Request)? {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+
+    if let Some(timeout) = settings.connect_timeout {
+        builder = builder.connect_timeout(Duration::from_secs(timeout));
+    }
+
+    if let Some(timeout) = settings.read_timeout {
+        builder = builder.timeout(Duration::from_secs(timeout));
+    }
+
+    builder.build().map_err(Error::Request)
+}
+
+/* -------------------------------------------------------------------------- */
+/*                         Function: get_fetch_options                        */
+/* -------------------------------------------------------------------------- */
+
+/// `get_fetch_options` returns the [git2::FetchOptions] used to fetch from the
+/// [Remote] repository, which authenticate with the remote (see
+/// [auth::callbacks]) and respect the user's network settings.
+pub(super) fn get_fetch_options(remote: &Remote) -> Result<git2::FetchOptions<'static>, Error> {
+    let mut opts = git2::FetchOptions::default();
+    opts.remote_callbacks(auth::callbacks(remote)?)
+        .proxy_options(get_proxy_options()?);
+
+    Ok(opts)
+}
+
+/* -------------------------------------------------------------------------- */
+/*                         Function: get_proxy_options                        */
+/* -------------------------------------------------------------------------- */
+
+/// `get_proxy_options` returns the [git2::ProxyOptions] for connecting to a
+/// remote repository. If no proxy is configured, then it's detected from git's
+/// configuration and the environment.
+pub(super) fn get_proxy_options() -> Result<git2::ProxyOptions<'static>, Error> {
+    configure_libgit2()?;
+
+    let mut opts = git2::ProxyOptions::new();
+
+    match super::get_settings()?.network.proxy.as_ref() {
+        Some(proxy) => opts.url(proxy.as_str().trim_end_matches('/')),
+        None => opts.auto(),
+    };
+
+    Ok(opts)
+}
+
+/* -------------------------------------------------------------------------- */
+/*                         Function: configure_libgit2                        */
+/* -------------------------------------------------------------------------- */
+
+/// `configure_libgit2` applies the user's network settings to the global
+/// `libgit2` options; this only happens once per process.
+///
+/// NOTE: `git2` doesn't expose `libgit2`'s timeout or user agent options, so
+/// only the certificate bundles are applied; git fetches use the defaults.
+fn configure_libgit2() -> Result<(), Error> {
+    static RESULT: OnceLock<Result<(), String>> = OnceLock::new();
+
+    let result = RESULT.get_or_init(|| {
+        let settings = &super::get_settings().map_err(|e| e.to_string())?.network;
+
+        for path in &settings.ca_bundles {
+            // SAFETY: This is called before any remote connections are made
+            // and is guarded against concurrent calls by the `OnceLock`.
+            unsafe { git2::opts::set_ssl_cert_file(auth::expand_home(path.clone())) }
+                .map_err(|e| e.to_string())?;
+        }
+
+        Ok(())
+    });
+
+    result
+        .clone()
+        .map_err(|e| Error::InvalidInput(format!("network settings: {}", e)))
+}
+
+/* -------------------------------------------------------------------------- */
+/*                           Function: with_retries                           */
+/* -------------------------------------------------------------------------- */
+
+/// `with_retries` calls `f` until it succeeds, retrying it with exponential
+/// backoff (see [get_backoff]) while it fails with a transient error (see
+/// [is_transient]), up to the number of retries configured by the user. Note
+/// that `f` must clean up after a failed attempt so that it can be restarted.
+pub(super) fn with_retries<T>(mut f: impl FnMut() -> Result<T, Error>) -> Result<T, Error> {
+    let retries = super::get_settings()?
+        .network
+        .retries
+        .unwrap_or(RETRY_DEFAULT_COUNT);
+
+    let mut attempt = 0;
+
+    loop {
+        match f() {
+            Err(e) if attempt < retries && is_transient(&e) => {
+                let delay = get_backoff(attempt);
+
+                println!(
+                    "request failed: {}; retrying in {:.1}s...",
+                    e,
+                    delay.as_secs_f32()
+                );
+
+                std::thread::sleep(delay);
+
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/* -------------------------- Function: get_backoff ------------------------- */
+
+/// `get_backoff` returns the delay before retrying a request which failed
+/// `attempt` times before, doubling with each attempt.
+fn get_backoff(attempt: u32) -> Duration {
+    RETRY_BACKOFF_INITIAL
+        .saturating_mul(2u32.saturating_pow(attempt))
+        .min(RETRY_BACKOFF_MAX)
+}
+
+/* ------------------------- Function: is_transient ------------------------- */
+
+/// `is_transient` returns whether the request which failed with `err` may
+/// succeed if it's retried, i.e. it failed due to a server error (`5xx` or
+/// `429`), a timeout, or a dropped connection.
+fn is_transient(err: &Error) -> bool {
+    match err {
+        Error::Response(status) => is_transient_status(status.as_u16()),
+        Error::Request(e) => is_transient_request(e),
+        Error::Io(e) => is_transient_io(e),
+        Error::Clone(_, e) | Error::Fetch(_, e) | Error::Git(e) => is_transient_git(e),
+        _ => false,
+    }
+}
+
+/// `is_transient_request` returns whether the HTTP request which failed with
+/// `err` timed out, couldn't connect, was cut off, or received a server error.
+fn is_transient_request(err: &reqwest::Error) -> bool {
+    if err.is_timeout() || err.is_connect() || err.is_body() {
+        return true;
+    }
+
+    if err
+        .status()
+        .is_some_and(|s| is_transient_status(s.as_u16()))
+    {
+        return true;
+    }
+
+    // A response body which was cut off is reported as a decoding error which
+    // is caused by an I/O error; other decoding errors aren't retried.
+    let mut source = std::error::Error::source(err);
+
+    while let Some(e) = source {
+        if e.downcast_ref::<std::io::Error>()
+            .is_some_and(is_transient_io)
+        {
+            return err.is_decode();
+        }
+
+        source = e.source();
+    }
+
+    false
+}
+
+/// `is_transient_io` returns whether the I/O error `err` was caused by a
+/// connection which was dropped or timed out.
+fn is_transient_io(err: &std::io::Error) -> bool {
+    matches!(
+        err.kind(),
+        std::io::ErrorKind::BrokenPipe
+            | std::io::ErrorKind::ConnectionAborted
+            | std::io::ErrorKind::ConnectionReset
+            | std::io::ErrorKind::TimedOut
+            | std::io::ErrorKind::UnexpectedEof
+    )
+}
+
+/// `is_transient_git` returns whether the `libgit2` error `err` was caused by a
+/// timeout, a dropped connection, or a server error. Only network errors are
+/// considered, except that a refused connection is reported as an OS error.
+fn is_transient_git(err: &git2::Error) -> bool {
+    let message = err.message().to_lowercase();
+
+    let is_network = matches!(
+        err.class(),
+        git2::ErrorClass::Net | git2::ErrorClass::Http | git2::ErrorClass::Ssh
+    );
+
+    if !is_network && !message.starts_with(GIT_CONNECT_PREFIX) {
+        return false;
+    }
+
+    if let Some(status) = message.strip_prefix(GIT_HTTP_STATUS_PREFIX) {
+        return status.trim().parse().is_ok_and(is_transient_status);
+    }
+
+    GIT_TRANSIENT_MESSAGES.iter().any(|m| message.contains(m))
+}
+
+/// `is_transient_status` returns whether an HTTP response with status code
+/// `status` may succeed if the request is retried.
+fn is_transient_status(status: u16) -> bool {
+    status == 429 || (500..600).contains(&status)
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Mod: Tests                                 */
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use std::time::Duration;
+
+    use super::get_backoff;
+    use super::is_transient;
+    use super::Error;
+
+    /* -------------------------- Test: get_backoff ------------------------- */
+
+    #[rstest]
+    #[case(0, Duration::from_millis(500))]
+    #[case(1, Duration::from_secs(1))]
+    #[case(3, Duration::from_secs(4))]
+    #[case(10, Duration::from_secs(30))]
+    #[case(u32::MAX, Duration::from_secs(30))]
+    fn test_get_backoff(#[case] attempt: u32, #[case] want: Duration) {
+        // Given: The number of previously failed attempts.
+        // When: The delay before the next attempt is computed.
+        let got = get_backoff(attempt);
+
+        // Then: The delay doubles with each attempt, up to a maximum.
+        assert_eq!(got, want);
+    }
+
+    /* ------------------------- Test: is_transient ------------------------- */
+
+    #[rstest]
+    #[case(Error::Response(reqwest::StatusCode::BAD_GATEWAY), true)]
+    #[case(Error::Response(reqwest::StatusCode::TOO_MANY_REQUESTS), true)]
+    #[case(Error::Response(reqwest::StatusCode::NOT_FOUND), false)]
+    #[case(Error::Io(std::io::ErrorKind::ConnectionReset.into()), true)]
+    #[case(Error::Io(std::io::ErrorKind::NotFound.into()), false)]
+    #[case(
+        git_error(git2::ErrorClass::Http, "unexpected http status code: 503"),
+        true
+    )]
+    #[case(
+        git_error(git2::ErrorClass::Http, "unexpected http status code: 404"),
+        false
+    )]
+    #[case(git_error(git2::ErrorClass::Net, "early EOF"), true)]
+    #[case(git_error(git2::ErrorClass::Net, "unsupported URL protocol"), false)]
+    #[case(
+        git_error(git2::ErrorClass::Os, "failed to connect to localhost"),
+        true
+    )]
+    #[case(
+        git_error(git2::ErrorClass::Os, "could not write to socket: disk full"),
+        false
+    )]
+    #[case(
+        git_error(git2::ErrorClass::Os, "failed to make directory: permission denied"),
+        false
+    )]
+    #[case(git_error(git2::ErrorClass::Ssh, "timed out waiting for socket"), true)]
+    #[case(Error::MissingInput("name".to_owned()), false)]
+    fn test_is_transient(#[case] err: Error, #[case] want: bool) {
+        // Given: An error returned by a network request.
+        // When: The error is classified.
+        let got = is_transient(&err);
+
+        // Then: Only errors which may not recur are retried.
+        assert_eq!(got, want);
+    }
+
+    /* ------------------------- Function: git_error ------------------------ */
+
+    fn git_error(class: git2::ErrorClass, message: &str) -> Error {
+        Error::Fetch(
+            "https://github.com/owner/repo".to_owned(),
+            git2::Error::new(git2::ErrorCode::GenericError, class, message),
+        )
+    }
+}
@@ -0,0 +1,472 @@
+use serde::Deserialize;
+use serde::Serialize;
+use sha2::Digest;
+use sha2::Sha256;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::process::Stdio;
+
+use super::Error;
+
+const GPG_FINGERPRINT_LEN: usize = 40;
+const GPG_LONG_KEY_ID_LEN: usize = 16;
+const GPG_STATUS_VALIDSIG: &str = "[GNUPG:] VALIDSIG ";
+const MINISIGN_COMMENT: &str = "untrusted comment:";
+const SIGNATURE_PGP: &str = "-----BEGIN PGP SIGNATURE-----";
+const SIGNATURE_SSH: &str = "-----BEGIN SSH SIGNATURE-----";
+
+/// The `ssh-keygen -Y` namespace used for signed git objects.
+pub(super) const SSH_NAMESPACE_GIT: &str = "git";
+/// The `ssh-keygen -Y` namespace used for signed files (e.g. release assets).
+pub(super) const SSH_NAMESPACE_FILE: &str = "file";
+
+/* -------------------------------------------------------------------------- */
+/*                               Struct: Verify                               */
+/* -------------------------------------------------------------------------- */
+
+/// `Verify` specifies the keys trusted to sign a dependency's git tags (or
+/// commits) and release assets. Signatures are verified using the `gpg`,
+/// `ssh-keygen`, and `minisign` command-line tools. The keys configured in the
+/// user's [`crate::config::settings::Settings`] are trusted as well.
+#[derive(Clone, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Verify {
+    /// Fingerprints (or long key IDs) of trusted GPG keys, which must be
+    /// present in the user's GPG keyring. Shorter key IDs are rejected since
+    /// they can be forged.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub gpg_keys: Vec<String>,
+    /// Paths to SSH "allowed signers" files (see `ssh-keygen(1)`); relative
+    /// paths are resolved against the directory of the declaring manifest.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ssh_allowed_signers: Vec<PathBuf>,
+    /// Trusted `minisign` public keys (e.g. `RWQf6LRCGA9i53mlYecO4IzT51TGPpvWucNSCh1CBM0QTaLn73Y7GFO3`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub minisign_keys: Vec<String>,
+}
+
+/* ------------------------------ Impl: Verify ------------------------------ */
+
+impl Verify {
+    /* --------------------------- Methods: Public -------------------------- */
+
+    /// `rooted_at` returns a new [Verify] with relative `ssh_allowed_signers`
+    /// paths joined onto `path`.
+    pub fn rooted_at(&self, path: impl AsRef<Path>) -> Verify {
+        let mut verify = self.clone();
+
+        verify.ssh_allowed_signers = verify
+            .ssh_allowed_signers
+            .into_iter()
+            .map(super::auth::expand_home)
+            .map(|p| match p.is_absolute() {
+                true => p,
+                false => path.as_ref().join(p),
+            })
+            .collect();
+
+        verify
+    }
+
+    /* -------------------------- Methods: Private -------------------------- */
+
+    /// `trusted` returns the union of these keys and those configured in the
+    /// user's settings. An error is returned if any GPG key is invalid (see
+    /// [normalize_gpg_key]) so that a misconfigured key is never ignored.
+    pub(super) fn trusted(&self) -> Result<Verify, Error> {
+        let mut verify = self.clone();
+
+        let settings = &super::get_settings()?.verify;
+        let settings = match super::get_store_path() {
+            Ok(path) => settings.rooted_at(path),
+            Err(_) => settings.clone(),
+        };
+
+        verify.gpg_keys.extend(settings.gpg_keys);
+        verify
+            .ssh_allowed_signers
+            .extend(settings.ssh_allowed_signers);
+        verify.minisign_keys.extend(settings.minisign_keys);
+
+        for key in &verify.gpg_keys {
+            normalize_gpg_key(key).map_err(Error::InvalidInput)?;
+        }
+
+        Ok(verify)
+    }
+
+    /// `digest` returns a checksum which identifies this set of trusted keys.
+    pub(super) fn digest(&self) -> String {
+        let contents = serde_json::to_vec(self).expect("failed to serialize keys");
+        format!("{:x}", Sha256::digest(contents))
+    }
+
+    /// `verify_object` verifies the signature of the annotated tag or commit
+    /// `id` within `repo`.
+    pub(super) fn verify_object(
+        &self,
+        repo: &git2::Repository,
+        id: git2::Oid,
+    ) -> Result<(), String> {
+        let (data, signature) = match repo.find_tag(id) {
+            Ok(_) => {
+                let odb = repo.odb().map_err(|e| e.to_string())?;
+                let obj = odb.read(id).map_err(|e| e.to_string())?;
+
+                split_tag_signature(obj.data())
+                    .map(|(d, s)| (d.to_owned(), s.to_owned()))
+                    .ok_or("tag is not signed")?
+            }
+            Err(_) => repo
+                .extract_signature(&id, None)
+                .map(|(s, d)| (d.to_vec(), s.to_vec()))
+                .map_err(|_| "commit is not signed")?,
+        };
+
+        self.verify(&data, &signature, SSH_NAMESPACE_GIT)
+    }
+
+    /// `verify` checks that `signature` is a valid signature of `data` by one
+    /// of the trusted keys. The kind of signature (i.e. GPG, SSH, or minisign)
+    /// is determined from its contents.
+    pub(super) fn verify(
+        &self,
+        data: &[u8],
+        signature: &[u8],
+        namespace: &str,
+    ) -> Result<(), String> {
+        if signature.starts_with(MINISIGN_COMMENT.as_bytes()) {
+            return self.verify_minisign(data, signature);
+        }
+
+        if signature.starts_with(SIGNATURE_SSH.as_bytes()) {
+            return self.verify_ssh(data, signature, namespace);
+        }
+
+        self.verify_gpg(data, signature)
+    }
+
+    /// `verify_gpg` verifies a GPG signature and then checks that the signing
+    /// key is one of the trusted `gpg_keys`.
+    fn verify_gpg(&self, data: &[u8], signature: &[u8]) -> Result<(), String> {
+        if self.gpg_keys.is_empty() {
+            return Err("no trusted gpg keys configured".into());
+        }
+
+        let tmp = tempfile::tempdir().map_err(|e| e.to_string())?;
+
+        let path_signature = tmp.path().join("signature.sig");
+        std::fs::write(&path_signature, signature).map_err(|e| e.to_string())?;
+
+        let mut cmd = Command::new("gpg");
+        cmd.args(["--batch", "--no-tty", "--status-fd", "1", "--verify"])
+            .arg(&path_signature)
+            .arg("-");
+
+        let output = run(cmd, data)?;
+
+        // Both the signing (sub)key and its primary key are reported.
+        let fingerprints = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|l| l.strip_prefix(GPG_STATUS_VALIDSIG))
+            .flat_map(|l| {
+                let fields = l.split_whitespace().collect::<Vec<_>>();
+                [fields.first().copied(), fields.get(9).copied()]
+            })
+            .flatten()
+            .map(str::to_uppercase)
+            .collect::<Vec<_>>();
+
+        if !output.status.success() || fingerprints.is_empty() {
+            return Err(format!(
+                "invalid gpg signature: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let keys = self
+            .gpg_keys
+            .iter()
+            .map(|k| normalize_gpg_key(k))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // A long key ID only matches the end of a full fingerprint; otherwise
+        // the fingerprint must match exactly.
+        let is_trusted = keys.iter().any(|key| {
+            fingerprints.iter().any(|f| match key.len() {
+                GPG_LONG_KEY_ID_LEN => f.len() == GPG_FINGERPRINT_LEN && f.ends_with(key),
+                _ => f == key,
+            })
+        });
+
+        match is_trusted {
+            true => Ok(()),
+            false => Err(format!("untrusted gpg key: {}", fingerprints[0])),
+        }
+    }
+
+    /// `verify_ssh` verifies an SSH signature against each of the trusted
+    /// `ssh_allowed_signers` files.
+    fn verify_ssh(&self, data: &[u8], signature: &[u8], namespace: &str) -> Result<(), String> {
+        if self.ssh_allowed_signers.is_empty() {
+            return Err("no ssh allowed signers configured".into());
+        }
+
+        let tmp = tempfile::tempdir().map_err(|e| e.to_string())?;
+
+        let path_signature = tmp.path().join("signature.sig");
+        std::fs::write(&path_signature, signature).map_err(|e| e.to_string())?;
+
+        for path in &self.ssh_allowed_signers {
+            let mut cmd = Command::new("ssh-keygen");
+            cmd.args(["-Y", "find-principals", "-f"])
+                .arg(path)
+                .arg("-s")
+                .arg(&path_signature);
+
+            let output = run(cmd, &[])?;
+            if !output.status.success() {
+                continue;
+            }
+
+            for principal in String::from_utf8_lossy(&output.stdout).lines() {
+                let mut cmd = Command::new("ssh-keygen");
+                cmd.args(["-Y", "verify", "-f"])
+                    .arg(path)
+                    .args(["-I", principal, "-n", namespace, "-s"])
+                    .arg(&path_signature);
+
+                if run(cmd, data)?.status.success() {
+                    return Ok(());
+                }
+            }
+        }
+
+        Err("ssh signature not made by an allowed signer".into())
+    }
+
+    /// `verify_minisign` verifies a `minisign` signature against each of the
+    /// trusted `minisign_keys`.
+    fn verify_minisign(&self, data: &[u8], signature: &[u8]) -> Result<(), String> {
+        if self.minisign_keys.is_empty() {
+            return Err("no trusted minisign keys configured".into());
+        }
+
+        let tmp = tempfile::tempdir().map_err(|e| e.to_string())?;
+
+        let path_data = tmp.path().join("data");
+        std::fs::write(&path_data, data).map_err(|e| e.to_string())?;
+
+        let path_signature = tmp.path().join("data.minisig");
+        std::fs::write(&path_signature, signature).map_err(|e| e.to_string())?;
+
+        for key in &self.minisign_keys {
+            let mut cmd = Command::new("minisign");
+            cmd.args(["-V", "-q", "-P", key, "-m"])
+                .arg(&path_data)
+                .arg("-x")
+                .arg(&path_signature);
+
+            if run(cmd, &[])?.status.success() {
+                return Ok(());
+            }
+        }
+
+        Err("minisign signature not made by a trusted key".into())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                Function: run                               */
+/* -------------------------------------------------------------------------- */
+
+/// `run` executes `cmd` with `stdin` as its input and returns its output.
+fn run(mut cmd: Command, stdin: &[u8]) -> Result<std::process::Output, String> {
+    let program = cmd.get_program().to_string_lossy().into_owned();
+
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("failed to run '{}': {}", program, e))?;
+
+    // NOTE: The input is written from a separate thread so that a command
+    // which doesn't read its input can't deadlock on a full pipe.
+    let mut input = child.stdin.take().expect("missing stdin");
+    let stdin = stdin.to_owned();
+    let writer = std::thread::spawn(move || input.write_all(&stdin));
+
+    let output = child
+        .wait_with_output()
+        .map_err(|e| format!("failed to run '{}': {}", program, e))?;
+
+    let _ = writer.join();
+
+    Ok(output)
+}
+
+/* -------------------------------------------------------------------------- */
+/*                        Function: split_tag_signature                       */
+/* -------------------------------------------------------------------------- */
+
+/// `split_tag_signature` splits the raw contents of an annotated tag object into
+/// its signed payload and its trailing signature, if it's signed.
+fn split_tag_signature(data: &[u8]) -> Option<(&[u8], &[u8])> {
+    [SIGNATURE_PGP, SIGNATURE_SSH]
+        .iter()
+        .filter_map(|armor| {
+            let needle = format!("\n{}", armor);
+            data.windows(needle.len())
+                .position(|w| w == needle.as_bytes())
+        })
+        .min()
+        .map(|i| data.split_at(i + 1))
+}
+
+/* -------------------------------------------------------------------------- */
+/*                         Function: normalize_gpg_key                        */
+/* -------------------------------------------------------------------------- */
+
+/// `normalize_gpg_key` converts a GPG key fingerprint or long key ID into the
+/// uppercase, unspaced form reported by `gpg --status-fd`. Any other form of
+/// key (e.g. a short key ID) is rejected.
+fn normalize_gpg_key(key: &str) -> Result<String, String> {
+    let trimmed = key.trim();
+    let trimmed = trimmed
+        .strip_prefix("0x")
+        .or_else(|| trimmed.strip_prefix("0X"))
+        .unwrap_or(trimmed);
+
+    let normalized = trimmed
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_uppercase();
+
+    let is_valid = [GPG_FINGERPRINT_LEN, GPG_LONG_KEY_ID_LEN].contains(&normalized.len())
+        && normalized.chars().all(|c| c.is_ascii_hexdigit());
+
+    match is_valid {
+        true => Ok(normalized),
+        false => Err(format!(
+            "gpg key '{}' isn't a {}-character fingerprint or {}-character long key ID",
+            key, GPG_FINGERPRINT_LEN, GPG_LONG_KEY_ID_LEN,
+        )),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+/*                                 Mod: Tests                                 */
+/* -------------------------------------------------------------------------- */
+
+#[cfg(test)]
+mod tests {
+    use rstest::rstest;
+    use std::path::PathBuf;
+
+    use super::normalize_gpg_key;
+    use super::split_tag_signature;
+    use super::Verify;
+
+    /* ----------------------- Test: Verify::rooted_at ---------------------- */
+
+    #[test]
+    fn test_verify_rooted_at_joins_relative_paths() {
+        // Given: Allowed signers files with relative and absolute paths.
+        let verify = Verify {
+            ssh_allowed_signers: vec![PathBuf::from("keys"), PathBuf::from("/etc/keys")],
+            ..Default::default()
+        };
+
+        // When: The paths are rooted at a directory.
+        let got = verify.rooted_at("/project");
+
+        // Then: Only the relative path is joined onto the directory.
+        assert_eq!(
+            got.ssh_allowed_signers,
+            vec![PathBuf::from("/project/keys"), PathBuf::from("/etc/keys")]
+        );
+    }
+
+    /* ------------------------ Test: Verify::trusted ----------------------- */
+
+    #[test]
+    fn test_verify_trusted_rejects_short_gpg_key() {
+        // Given: A dependency which trusts a short GPG key ID.
+        let verify = Verify {
+            gpg_keys: vec![String::from("B80A6FFD")],
+            ..Default::default()
+        };
+
+        // When: The trusted keys are determined.
+        let got = verify.trusted();
+
+        // Then: The key is rejected rather than ignored.
+        assert!(got.is_err_and(|e| e.to_string().contains("gpg key 'B80A6FFD'")));
+    }
+
+    /* ---------------------- Test: split_tag_signature --------------------- */
+
+    #[test]
+    fn test_split_tag_signature_separates_payload() {
+        // Given: The contents of a signed annotated tag.
+        let data = b"object abc\ntype commit\ntag v1.0.0\n\nRelease\n-----BEGIN SSH SIGNATURE-----\nxyz\n-----END SSH SIGNATURE-----\n";
+
+        // When: The signature is split from the tag.
+        let got = split_tag_signature(data);
+
+        // Then: The payload ends just before the signature.
+        let (payload, signature) = got.unwrap();
+        assert_eq!(payload, b"object abc\ntype commit\ntag v1.0.0\n\nRelease\n");
+        assert!(signature.starts_with(b"-----BEGIN SSH SIGNATURE-----"));
+    }
+
+    #[test]
+    fn test_split_tag_signature_of_unsigned_tag_is_none() {
+        // Given: The contents of an unsigned annotated tag.
+        let data = b"object abc\ntype commit\ntag v1.0.0\n\nRelease\n";
+
+        // When: The signature is split from the tag.
+        let got = split_tag_signature(data);
+
+        // Then: There's no signature.
+        assert_eq!(got, None);
+    }
+
+    /* ----------------------- Test: normalize_gpg_key ---------------------- */
+
+    #[rstest]
+    #[case("0xabcdef0123456789", "ABCDEF0123456789")]
+    #[case("ABCD EF01 2345 6789", "ABCDEF0123456789")]
+    #[case(
+        " be52 4903 1363 3553 255f 49ba 7ac0 0ac0 b80a 6ffd ",
+        "BE52490313633553255F49BA7AC00AC0B80A6FFD"
+    )]
+    fn test_normalize_gpg_key(#[case] input: &str, #[case] want: &str) {
+        // Given: A GPG key identifier in any supported format.
+        // When: The key is normalized.
+        let got = normalize_gpg_key(input);
+
+        // Then: The key matches the format reported by 'gpg'.
+        assert_eq!(got.unwrap(), want);
+    }
+
+    #[rstest]
+    #[case("")]
+    #[case("abc")]
+    #[case("0xB80A6FFD")]
+    #[case("ABCDEF012345678")]
+    #[case("GHIJKL0123456789")]
+    #[case("BE52490313633553255F49BA7AC00AC0B80A6FF")]
+    fn test_normalize_gpg_key_rejects_short_or_invalid_keys(#[case] input: &str) {
+        // Given: A GPG key identifier which isn't a fingerprint or long key ID.
+        // When: The key is normalized.
+        let got = normalize_gpg_key(input);
+
+        // Then: The key is rejected.
+        assert!(got.is_err());
+    }
+}